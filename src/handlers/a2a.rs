@@ -1,35 +1,50 @@
 use crate::models::a2a::{A2ARequest, A2AResponse, TelexMessage, MessagePart};
-use crate::models::scan::{ScanState, ScanStatus};
+use crate::models::baseline::{Baseline, BASELINE_FILE_NAME};
+use crate::models::scan::{Finding, FindingStatus, OrgScanFilter, RepoScanSummary, ScanMode, ScanState, ScanStats, ScanStatus, SkipReason};
+use crate::models::source::{ProviderKind, RepoRef, SourceCommit};
 use crate::services::github::GitHubClient;
+use crate::services::provider::{ProviderRegistry, SourceProvider};
 use crate::services::scanner::SecretScanner;
-use crate::services::gemini::GeminiClient;
+use crate::services::gemini::{GeminiClient, ScanCommand};
 use crate::services::state::StateManager;
+use crate::services::verifier::CredentialVerifier;
+use crate::handlers::findings::{TriageToken, authenticated_actor};
+use crate::utils::logging::body_preview;
 use actix_web::{web, HttpResponse, HttpRequest, Result as ActixResult};
-use chrono::Utc;
+use chrono::{DateTime, Utc};
+use futures::stream::{self, StreamExt};
 use uuid::Uuid;
+use std::collections::HashSet;
 use std::sync::Arc;
-use log::{info, error};
+use log::{info, warn, error};
+
+const CHAT_MESSAGE_PATH: &str = "chat message";
 
 pub struct AppState {
     pub gemini_client: Arc<GeminiClient>,
     pub github_client: Arc<GitHubClient>,
+    pub providers: ProviderRegistry,
     pub state_manager: Arc<StateManager>,
     pub scanner: Arc<SecretScanner>,
+    pub verifier: Option<Arc<CredentialVerifier>>,
     pub max_scan_commits: u32,
+    pub org_scan_concurrency: usize,
+    pub scan_concurrency: usize,
+    pub triage_tokens: Vec<TriageToken>,
 }
 
 pub async fn handle_a2a_request(
-    _req: HttpRequest,
+    req: HttpRequest,
     body: web::Bytes,
     data: web::Data<AppState>,
 ) -> ActixResult<HttpResponse> {
     let body_str = String::from_utf8_lossy(&body);
-    info!("Received request body: {}", body_str);
+    info!("Received request body: {}", body_preview(&body_str));
     
     let a2a_request: A2ARequest = match serde_json::from_slice(&body) {
         Ok(r) => r,
         Err(e) => {
-            error!("Failed to parse A2A request: {}. Body was: {}", e, body_str);
+            error!("Failed to parse A2A request: {}. Body was: {}", e, body_preview(&body_str));
             return Ok(HttpResponse::BadRequest().json(
                 A2AResponse::error("unknown".to_string(), -32700, format!("Parse error: {}", e))
             ));
@@ -46,7 +61,31 @@ pub async fn handle_a2a_request(
     let task_id = Uuid::new_v4().to_string();
     let context_id = Uuid::new_v4().to_string();
     
-    match process_request(&a2a_request, &data).await {
+    let user_message = match extract_user_message(&a2a_request.params.message) {
+        Ok(message) => message,
+        Err(e) => {
+            error!("Request processing failed: {}", e);
+            return Ok(HttpResponse::Ok().json(
+                A2AResponse::error(request_id, -32603, format!("Internal error: {}", e))
+            ));
+        }
+    };
+    
+    // A message carrying a credential is answered here: it never reaches Gemini or the state database, and the
+    // history in the reply carries a placeholder instead of the message.
+    let pasted = pasted_secrets(&user_message, &data);
+    if !pasted.is_empty() {
+        warn!("Incoming message contains {} secret(s); not processing it", pasted.len());
+        return Ok(HttpResponse::Ok().json(A2AResponse::success(
+            request_id,
+            task_id,
+            context_id,
+            pasted_secret_warning(&pasted),
+            &a2a_request.params.message.withheld(),
+        )));
+    }
+    
+    match process_request(&req, &a2a_request, &user_message, &data).await {
         Ok(response_text) => {
             let response = A2AResponse::success(
                 request_id,
@@ -67,35 +106,62 @@ pub async fn handle_a2a_request(
 }
 
 async fn process_request(
+    http_req: &HttpRequest,
     req: &A2ARequest,
+    user_message: &str,
     data: &web::Data<AppState>,
 ) -> anyhow::Result<String> {
-    let user_message = extract_user_message(&req.params.message)?;
-    
-    info!("Processing request: {}", user_message);
+    info!("Processing request: {}", body_preview(user_message));
     
-    let command = data.gemini_client.parse_user_intent(&user_message, &[]).await?;
+    let command = data.gemini_client.parse_user_intent(user_message, &[]).await?;
     
     info!("Parsed command - action: {}, mode: {}", command.action, command.scan_mode);
     
+    let conversation_id = req.params.message.task_id.as_deref();
+    let repo_url = match (&command.repo_url, conversation_id) {
+        (Some(url), Some(id)) => {
+            data.state_manager.save_conversation(id, url).await?;
+            Some(url.clone())
+        }
+        (Some(url), None) => Some(url.clone()),
+        (None, Some(id)) => data.state_manager.load_conversation(id).await?,
+        (None, None) => None,
+    };
+    
     let response_text = match command.action.as_str() {
         "start_scan" => {
             if let Some(ref repo_url) = command.repo_url {
-                info!("Starting scan for: {}", repo_url);
-                execute_scan(repo_url, &command.scan_mode, data).await?
+                if let Ok(owner) = data.github_client.parse_owner_url(repo_url) {
+                    info!("Starting organization scan for: {}", owner);
+                    execute_org_scan(&owner, repo_url, &command, data).await?
+                } else {
+                    info!("Starting scan for: {}", repo_url);
+                    execute_scan(repo_url, &command, data).await?
+                }
             } else {
-                "Please provide a GitHub repository URL to scan.".to_string()
+                "Please provide a GitHub or GitLab repository URL to scan.".to_string()
             }
         }
         "continue_scan" => {
-            if let Some(ref repo_url) = command.repo_url {
+            if let Some(ref repo_url) = repo_url {
                 continue_scan(repo_url, data).await?
             } else {
                 "Please specify which repository to continue scanning.".to_string()
             }
         }
+        // Triage from chat needs the same bearer token as the findings API; the task id is chosen by the client.
+        "update_finding" => match authenticated_actor(http_req, &data.triage_tokens) {
+            Some(actor) => update_finding(repo_url.as_deref(), &command, actor, data).await?,
+            None => {
+                warn!("Rejected unauthenticated triage request from chat");
+                "Triaging findings needs a bearer token from TRIAGE_TOKENS in the Authorization header of the request.".to_string()
+            }
+        },
         "status" => {
-            get_scan_status(data).await?
+            match command.repo_url {
+                Some(ref repo_url) => get_repo_findings(repo_url, data).await?,
+                None => get_scan_status(data).await?,
+            }
         }
         "help" => {
             get_help_message()
@@ -114,7 +180,7 @@ fn extract_user_message(message: &TelexMessage) -> anyhow::Result<String> {
     for (i, part) in message.parts.iter().enumerate() {
         match part {
             MessagePart::Text { text, .. } => {
-                info!("Part {}: Text = '{}'", i, text);
+                info!("Part {}: Text = '{}'", i, body_preview(text));
                 if !text.trim().is_empty() {
                     return Ok(text.clone());
                 }
@@ -122,7 +188,7 @@ fn extract_user_message(message: &TelexMessage) -> anyhow::Result<String> {
             MessagePart::Data { data, .. } => {
                 info!("Part {}: Data with {} items", i, data.len());
                 for (j, item) in data.iter().enumerate() {
-                    info!("  Data[{}]: {}", j, item);
+                    info!("  Data[{}]: {}", j, body_preview(&item.to_string()));
                     if let Some(text) = item.get("text").and_then(|v| v.as_str()) {
                         let text = text.trim();
                         if !text.is_empty() 
@@ -130,7 +196,7 @@ fn extract_user_message(message: &TelexMessage) -> anyhow::Result<String> {
                             && !text.starts_with("Scanning")
                             && !text.starts_with("Here")
                             && text.len() > 5 {
-                            info!("Found message in data: '{}'", text);
+                            info!("Found message in data: '{}'", body_preview(text));
                             return Ok(text.to_string());
                         }
                     }
@@ -144,46 +210,268 @@ fn extract_user_message(message: &TelexMessage) -> anyhow::Result<String> {
 
 async fn execute_scan(
     repo_url: &str,
-    scan_mode: &str,
+    command: &ScanCommand,
     data: &web::Data<AppState>,
 ) -> anyhow::Result<String> {
     info!("Parsing repo URL: {}", repo_url);
-    let (owner, repo) = GitHubClient::parse_repo_url(repo_url)?;
+    let (provider, repo) = data.providers.resolve(repo_url)?;
     
-    info!("Fetching repository info for {}/{}", owner, repo);
-    let _repository = data.github_client.get_repository(&owner, &repo).await?;
+    info!("Fetching repository info for {}", repo.full_name());
+    let repository = provider.get_repository(&repo).await?;
     
-    info!("Listing commits for {}/{}", owner, repo);
-    let commits = data.github_client.list_commits(
-        &owner,
-        &repo,
+    let (mut all_findings, commit_shas, mut stats) = scan_repository(provider.as_ref(), &repo, data).await?;
+    
+    if provider.kind() == ProviderKind::GitHub
+        && (command.include_discussions || command.scan_mode == "deep")
+    {
+        all_findings.extend(scan_discussions(&repo.owner, &repo.repo, &mut stats, data).await);
+    }
+    
+    record_completed_scan(repo_url, &repo, ScanMode::from(command.scan_mode.as_str()), &commit_shas, &all_findings, &stats, data).await?;
+    let mut all_findings = apply_triage(repo_url, all_findings, data).await?;
+    
+    if let Some(baseline) = load_repo_baseline(provider.as_ref(), &repo, repository.default_branch.as_deref()).await {
+        let (new_findings, suppressed) = baseline.filter_new(all_findings);
+        info!("Baseline suppressed {} known findings for {}", suppressed, repo_url);
+        all_findings = new_findings;
+    }
+    
+    info!("Generating response with Gemini");
+    let response = data.gemini_client.generate_response(
+        &all_findings,
+        repo_url,
+        &command.scan_mode,
+        commit_shas.len(),
+    ).await?;
+    
+    info!("Response generated successfully");
+    Ok(response)
+}
+
+pub(crate) async fn load_repo_baseline(
+    provider: &dyn SourceProvider,
+    repo: &RepoRef,
+    default_branch: Option<&str>,
+) -> Option<Baseline> {
+    let git_ref = default_branch.unwrap_or("HEAD");
+    let content = provider.get_file_content(repo, BASELINE_FILE_NAME, git_ref).await.ok()?;
+    
+    match serde_json::from_slice::<Baseline>(&content) {
+        Ok(baseline) => Some(baseline),
+        Err(e) => {
+            warn!("Ignoring unreadable {} in {}: {}", BASELINE_FILE_NAME, repo.full_name(), e);
+            None
+        }
+    }
+}
+
+pub(crate) async fn scan_repository(
+    provider: &dyn SourceProvider,
+    repo: &RepoRef,
+    data: &web::Data<AppState>,
+) -> anyhow::Result<(Vec<Finding>, Vec<String>, ScanStats)> {
+    info!("Listing commits for {}", repo.full_name());
+    let commits = provider.list_commits(
+        repo,
         None,
         data.max_scan_commits,  // Use from AppState
     ).await?;
     
     info!("Found {} commits to scan", commits.len());
     
-    let mut all_findings = Vec::new();
+    let (all_findings, stats) = scan_commits(provider, repo, &commits, data).await?;
+    
+    info!("Total findings for {}: {} ({})", repo.full_name(), all_findings.len(), stats.summary());
+    Ok((all_findings, commits.into_iter().map(|c| c.sha).collect(), stats))
+}
+
+pub(crate) async fn record_completed_scan(
+    repo_url: &str,
+    repo: &RepoRef,
+    scan_mode: ScanMode,
+    commit_shas: &[String],
+    findings: &[Finding],
+    stats: &ScanStats,
+    data: &web::Data<AppState>,
+) -> anyhow::Result<()> {
+    let previous = data.state_manager.load_state(repo_url).await?;
+    let mut total_stats = previous.as_ref().map(|p| p.stats.clone()).unwrap_or_default();
+    total_stats.merge(stats);
+    let state = ScanState {
+        repo_url: repo_url.to_string(),
+        owner: repo.owner.clone(),
+        repo: repo.repo.clone(),
+        scan_mode,
+        last_scanned_commit_sha: commit_shas.first().cloned()
+            .or_else(|| previous.as_ref().map(|p| p.last_scanned_commit_sha.clone()))
+            .unwrap_or_default(),
+        last_scan_timestamp: Utc::now(),
+        // Both counts are recomputed from the stored commits and tracked findings by `record_scan`.
+        total_commits_scanned: previous.as_ref().map_or(0, |p| p.total_commits_scanned),
+        findings_count: previous.as_ref().map_or(0, |p| p.findings_count),
+        status: ScanStatus::Completed,
+        stats: total_stats,
+    };
+    
+    data.state_manager.record_scan(&state, commit_shas, findings).await
+}
+
+async fn scan_commits(
+    provider: &dyn SourceProvider,
+    repo: &RepoRef,
+    commits: &[SourceCommit],
+    data: &web::Data<AppState>,
+) -> anyhow::Result<(Vec<Finding>, ScanStats)> {
+    let total = commits.len();
+    
+    // `buffered` keeps results in commit order even though fetches overlap.
+    let per_commit_results: Vec<anyhow::Result<(Vec<Finding>, ScanStats)>> = stream::iter(commits.iter().enumerate())
+        .map(|(idx, commit)| async move {
+            info!("Scanning commit {}/{}: {}", idx + 1, total, commit.sha);
+            let commit_details = data.scanner.fetch_commit(provider, repo, &commit.sha).await?;
+            let (findings, stats) = data.scanner.scan_commit(&commit_details, provider, repo).await?;
+            info!("Found {} secrets in commit {}", findings.len(), commit.sha);
+            Ok((findings, stats))
+        })
+        .buffered(data.scan_concurrency.max(1))
+        .collect()
+        .await;
     
-    for (idx, commit) in commits.iter().enumerate() {
-        info!("Scanning commit {}/{}: {}", idx + 1, commits.len(), commit.sha);
-        let commit_details = data.github_client.get_commit(&owner, &repo, &commit.sha).await?;
-        let findings = data.scanner.scan_commit(&commit_details, &data.github_client, &owner, &repo).await?;  // Use from AppState
-        info!("Found {} secrets in commit {}", findings.len(), commit.sha);
+    let mut all_findings = Vec::new();
+    let mut stats = ScanStats::default();
+    for result in per_commit_results {
+        let (findings, commit_stats) = result?;
         all_findings.extend(findings);
+        stats.merge(&commit_stats);
     }
     
-    info!("Total findings: {}", all_findings.len());
+    if let Some(verifier) = &data.verifier {
+        verifier.verify_findings(&mut all_findings).await;
+    }
     
-    info!("Generating response with Gemini");
-    let response = data.gemini_client.generate_response(
-        &all_findings,
-        repo_url,
-        scan_mode,
-        commits.len(),
+    Ok((all_findings, stats))
+}
+
+// Each source is listed on its own: a repo with issues disabled, or a wiki that fails to clone, is logged and
+// recorded in `stats` without failing the rest of the scan.
+async fn scan_discussions(
+    owner: &str,
+    repo: &str,
+    stats: &mut ScanStats,
+    data: &web::Data<AppState>,
+) -> Vec<Finding> {
+    info!("Collecting issues, comments, gists and wiki pages for {}/{}", owner, repo);
+    let github = &data.github_client;
+    let sources = [
+        ("issues", github.list_issue_content(owner, repo, stats).await),
+        ("issue comments", github.list_issue_comment_content(owner, repo, stats).await),
+        ("review comments", github.list_review_comment_content(owner, repo, stats).await),
+        ("gists", github.list_gist_content(owner, data.scanner.max_file_bytes(), stats).await),
+        ("wiki", github.list_wiki_content(owner, repo).await),
+    ];
+    
+    let mut contents = Vec::new();
+    for (source, result) in sources {
+        match result {
+            Ok(items) => contents.extend(items),
+            Err(e) => {
+                warn!("Could not list {} for {}/{}: {}", source, owner, repo, e);
+                stats.record_skipped(SkipReason::SourceUnavailable);
+            }
+        }
+    }
+    
+    let mut findings: Vec<Finding> = contents
+        .iter()
+        .flat_map(|content| data.scanner.scan_source_content(content, stats))
+        .collect();
+    
+    if let Some(verifier) = &data.verifier {
+        verifier.verify_findings(&mut findings).await;
+    }
+    
+    info!("Found {} secrets in {} discussion items for {}/{}", findings.len(), contents.len(), owner, repo);
+    findings
+}
+
+async fn execute_org_scan(
+    owner: &str,
+    owner_url: &str,
+    command: &ScanCommand,
+    data: &web::Data<AppState>,
+) -> anyhow::Result<String> {
+    let filter = OrgScanFilter {
+        include_forks: command.include_forks,
+        include_archived: command.include_archived,
+        language: command.language.clone(),
+        pushed_after: command.pushed_after.as_deref().and_then(|d| {
+            DateTime::parse_from_rfc3339(d)
+                .map(|d| d.with_timezone(&Utc))
+                .map_err(|e| warn!("Ignoring invalid pushed_after date '{}': {}", d, e))
+                .ok()
+        }),
+    };
+    
+    info!("Listing repositories for {}", owner);
+    let repositories = data.github_client.list_owner_repositories(owner).await?;
+    let total_repositories = repositories.len();
+    let repositories: Vec<_> = repositories
+        .into_iter()
+        .filter(|r| filter.matches(r))
+        .collect();
+    
+    info!(
+        "Scanning {} of {} repositories for {} with concurrency {}",
+        repositories.len(), total_repositories, owner, data.org_scan_concurrency
+    );
+    
+    let mut summaries: Vec<RepoScanSummary> = stream::iter(repositories)
+        .map(|repository| async move {
+            let repo = RepoRef {
+                owner: repository.owner.login,
+                repo: repository.name,
+            };
+            let scan = match scan_repository(data.github_client.as_ref(), &repo, data).await {
+                Ok((findings, commit_shas, stats)) => {
+                    match record_completed_scan(&repository.html_url, &repo, ScanMode::from(command.scan_mode.as_str()), &commit_shas, &findings, &stats, data).await {
+                        Ok(()) => apply_triage(&repository.html_url, findings, data)
+                            .await
+                            .map(|findings| (findings, commit_shas.len())),
+                        Err(e) => Err(e),
+                    }
+                }
+                Err(e) => Err(e),
+            };
+            match scan {
+                Ok((findings, commits_scanned)) => RepoScanSummary {
+                    repo_url: repository.html_url,
+                    commits_scanned,
+                    findings,
+                    error: None,
+                },
+                Err(e) => {
+                    error!("Scan of {} failed: {}", repo.full_name(), e);
+                    RepoScanSummary {
+                        repo_url: repository.html_url,
+                        commits_scanned: 0,
+                        findings: Vec::new(),
+                        error: Some(e.to_string()),
+                    }
+                }
+            }
+        })
+        .buffer_unordered(data.org_scan_concurrency.max(1))
+        .collect()
+        .await;
+    
+    summaries.sort_by(|a, b| a.repo_url.cmp(&b.repo_url));
+    
+    let response = data.gemini_client.generate_org_response(
+        &summaries,
+        owner_url,
+        &command.scan_mode,
     ).await?;
     
-    info!("Response generated successfully");
     Ok(response)
 }
 
@@ -194,35 +482,43 @@ async fn continue_scan(
     let state = data.state_manager.load_state(repo_url).await?
         .ok_or_else(|| anyhow::anyhow!("No previous scan found for this repository"))?;
     
-    let commits = data.github_client.list_commits(
-        &state.owner,
-        &state.repo,
+    let (provider, _) = data.providers.resolve(&state.repo_url)?;
+    let repo = RepoRef {
+        owner: state.owner.clone(),
+        repo: state.repo.clone(),
+    };
+    
+    let already_scanned: HashSet<String> = data.state_manager.scanned_commits(&state.repo_url).await?
+        .into_iter()
+        .collect();
+    let commits: Vec<SourceCommit> = provider.list_commits(
+        &repo,
         Some(&state.last_scan_timestamp.to_rfc3339()),
         data.max_scan_commits,
-    ).await?;
+    ).await?
+        .into_iter()
+        .filter(|c| !already_scanned.contains(&c.sha))
+        .collect();
     
     if commits.is_empty() {
         return Ok("No new commits to scan since last scan.".to_string());
     }
     
-    let mut all_findings = Vec::new();
-    
-    for commit in &commits {
-        let commit_details = data.github_client.get_commit(&state.owner, &state.repo, &commit.sha).await?;
-        let findings = data.scanner.scan_commit(&commit_details, &data.github_client, &state.owner, &state.repo).await?;  // Use from AppState
-        all_findings.extend(findings);
-    }
+    let (all_findings, stats) = scan_commits(provider.as_ref(), &repo, &commits, data).await?;
+    let commit_shas: Vec<String> = commits.iter().map(|c| c.sha.clone()).collect();
+    let mut total_stats = state.stats.clone();
+    total_stats.merge(&stats);
     
     let updated_state = ScanState {
-        last_scanned_commit_sha: commits.first().map(|c| c.sha.clone()).unwrap_or(state.last_scanned_commit_sha),
+        last_scanned_commit_sha: commit_shas.first().cloned().unwrap_or(state.last_scanned_commit_sha),
         last_scan_timestamp: Utc::now(),
-        total_commits_scanned: state.total_commits_scanned + commits.len(),
-        findings_count: state.findings_count + all_findings.len(),
         status: ScanStatus::Completed,
+        stats: total_stats,
         ..state
     };
     
-    data.state_manager.save_state(&updated_state).await?;
+    data.state_manager.record_scan(&updated_state, &commit_shas, &all_findings).await?;
+    let all_findings = apply_triage(repo_url, all_findings, data).await?;
     
     let response = data.gemini_client.generate_response(
         &all_findings,
@@ -245,23 +541,164 @@ async fn get_scan_status(data: &web::Data<AppState>) -> anyhow::Result<String> {
     
     for state in states {
         status_text.push_str(&format!(
-            "- {}: {} commits scanned, {} findings\n",
-            state.repo_url, state.total_commits_scanned, state.findings_count
+            "- {}: {} commits scanned, {} findings ({})\n",
+            state.repo_url, state.total_commits_scanned, state.findings_count, state.stats.summary()
         ));
     }
     
     Ok(status_text)
 }
 
+async fn update_finding(
+    repo_url: Option<&str>,
+    command: &ScanCommand,
+    actor: &str,
+    data: &web::Data<AppState>,
+) -> anyhow::Result<String> {
+    let Some(repo_url) = repo_url else {
+        return Ok("Please specify which repository the finding belongs to.".to_string());
+    };
+    let Some(fingerprint) = command.fingerprint.as_deref() else {
+        return Ok("Please give the fingerprint of the finding (shown in brackets in the status report).".to_string());
+    };
+    let Some(status) = command.finding_status.as_deref().and_then(FindingStatus::parse) else {
+        return Ok("Please say whether the finding is acknowledged, a false positive, rotated, or should be reopened.".to_string());
+    };
+    let reason = command.reason.as_deref().unwrap_or("no reason given");
+    
+    let finding = data.state_manager.set_finding_status(repo_url, fingerprint, status, reason, actor).await?;
+    info!("{} marked finding {} in {} as {:?}", actor, finding.fingerprint, repo_url, status);
+    
+    Ok(format!(
+        "Marked {} in {} ({}) as {:?}: {}",
+        finding.secret_type, finding.file_path, &finding.fingerprint[..finding.fingerprint.len().min(12)], status, reason
+    ))
+}
+
+pub(crate) async fn apply_triage(
+    repo_url: &str,
+    findings: Vec<Finding>,
+    data: &web::Data<AppState>,
+) -> anyhow::Result<Vec<Finding>> {
+    let triaged = data.state_manager.triaged_findings(repo_url).await?;
+    if triaged.is_empty() {
+        return Ok(findings);
+    }
+    
+    let total = findings.len();
+    let reported: Vec<Finding> = findings
+        .into_iter()
+        .filter_map(|mut finding| {
+            match triaged.get(&finding.fingerprint) {
+                Some(triage) if triage.status.suppresses() => None,
+                Some(triage) => {
+                    finding.triage = Some(triage.clone());
+                    Some(finding)
+                }
+                None => Some(finding),
+            }
+        })
+        .collect();
+    
+    info!("Suppressed {} previously triaged findings for {}", total - reported.len(), repo_url);
+    Ok(reported)
+}
+
+async fn get_repo_findings(repo_url: &str, data: &web::Data<AppState>) -> anyhow::Result<String> {
+    let findings = data.state_manager.list_findings(repo_url, None).await?;
+    
+    if findings.is_empty() {
+        return Ok(format!("No findings recorded for {}. Run a scan first.", repo_url));
+    }
+    
+    let open: Vec<_> = findings.iter().filter(|f| !f.status.suppresses()).collect();
+    let resolved: Vec<_> = findings.iter().filter(|f| f.status == FindingStatus::Resolved && f.triage.is_none()).collect();
+    let triaged: Vec<_> = findings.iter().filter(|f| f.status.suppresses() && f.triage.is_some()).collect();
+    
+    let mut status_text = format!("{} outstanding secrets in {}:\n\n", open.len(), repo_url);
+    for finding in &open {
+        status_text.push_str(&format!(
+            "- [{}] {} ({:?}) in {} line {}, first seen {} in {}, last seen {} in {}\n",
+            &finding.fingerprint[..finding.fingerprint.len().min(12)],
+            finding.secret_type,
+            finding.severity,
+            finding.file_path,
+            finding.line_number,
+            finding.first_seen.format("%Y-%m-%d"),
+            &finding.first_commit_sha[..finding.first_commit_sha.len().min(7)],
+            finding.last_seen.format("%Y-%m-%d"),
+            &finding.last_commit_sha[..finding.last_commit_sha.len().min(7)],
+        ));
+        if let Some(triage) = &finding.triage {
+            status_text.push_str(&format!("  acknowledged by {}: {}\n", triage.actor, triage.reason));
+        }
+    }
+    
+    if !resolved.is_empty() {
+        status_text.push_str(&format!("\n{} secrets were removed in later commits:\n\n", resolved.len()));
+        for finding in &resolved {
+            status_text.push_str(&format!(
+                "- [{}] {} in {}, removed in {} (rotate it anyway, it is still in git history)\n",
+                &finding.fingerprint[..finding.fingerprint.len().min(12)],
+                finding.secret_type,
+                finding.file_path,
+                finding.resolved_commit_sha.as_deref().map(|s| &s[..s.len().min(7)]).unwrap_or("a later commit"),
+            ));
+        }
+    }
+    
+    if !triaged.is_empty() {
+        status_text.push_str(&format!("\n{} findings were triaged and are suppressed:\n\n", triaged.len()));
+        for finding in &triaged {
+            if let Some(triage) = &finding.triage {
+                status_text.push_str(&format!(
+                    "- [{}] {} in {}: {:?} by {} ({})\n",
+                    &finding.fingerprint[..finding.fingerprint.len().min(12)],
+                    finding.secret_type,
+                    finding.file_path,
+                    triage.status,
+                    triage.actor,
+                    triage.reason,
+                ));
+            }
+        }
+    }
+    
+    Ok(status_text)
+}
+
+fn pasted_secrets(message: &str, data: &web::Data<AppState>) -> Vec<Finding> {
+    let mut stats = ScanStats::default();
+    data.scanner.scan_content(message, CHAT_MESSAGE_PATH, "", Utc::now(), &mut stats)
+}
+
+// Names the kind of secret and where it is, but never the value, not even redacted.
+fn pasted_secret_warning(findings: &[Finding]) -> String {
+    let mut kinds: Vec<String> = findings
+        .iter()
+        .map(|f| format!("- {} ({:?}) on line {}", f.secret_type, f.severity, f.line_number))
+        .collect();
+    kinds.dedup();
+
+    format!(
+        "Your message looks like it contains a secret:\n{}\n\nI haven't processed, stored or forwarded it. Treat it as exposed: rotate it, then send your request again without the credential.",
+        kinds.join("\n")
+    )
+}
+
 fn get_help_message() -> String {
-    r#"I can help you scan GitHub repositories for exposed secrets!
+    r#"I can help you scan GitHub and GitLab repositories for exposed secrets!
 
 Commands:
 - "scan <repo-url>" - Quick scan (last 100 commits)
+- "scan <org-or-user-url>" - Scan every repository of an organization or user
 - "start running scan <repo-url>" - Begin incremental scanning
 - "continue scan" - Continue previous running scan
-- "deep scan <repo-url>" - Full history scan
+- "deep scan <repo-url>" - Full history scan, plus issues, comments, gists and wiki
+- "scan <repo-url> including issues" - Also scan issues, PR comments, gists and wiki
 - "status" - Check current scan states
+- "status <repo-url>" - List outstanding secrets for a repository
+- "mark <fingerprint> in <repo-url> as false positive/rotated/acknowledged because ..." - Triage a finding
 
 I detect:
 - AWS credentials
@@ -269,7 +706,8 @@ I detect:
 - Database credentials
 - OAuth tokens
 - Private keys
+- Secrets pasted into issues, PR comments, gists and wikis
 - And more!
 
-Just provide a GitHub repository URL and I'll get started!"#.to_string()
+Just provide a GitHub or GitLab repository URL and I'll get started!"#.to_string()
 }
\ No newline at end of file
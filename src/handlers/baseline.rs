@@ -0,0 +1,107 @@
+use crate::handlers::a2a::{AppState, apply_triage, load_repo_baseline, record_completed_scan, scan_repository};
+use crate::models::baseline::{Baseline, BASELINE_FILE_NAME};
+use crate::models::scan::{Finding, RejectedCandidate, ScanMode, ScanStats};
+use crate::utils::patterns::RULE_PACK_VERSION;
+use actix_web::{web, HttpResponse, Result as ActixResult};
+use serde::{Deserialize, Serialize};
+use log::{info, error};
+
+#[derive(Debug, Deserialize)]
+pub struct BaselineQuery {
+    pub repo_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ScanRequest {
+    pub repo_url: String,
+    #[serde(default)]
+    pub scan_mode: Option<String>,
+    #[serde(default)]
+    pub baseline: Option<Baseline>,
+    #[serde(default)]
+    pub debug: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ScanReport {
+    pub repo_url: String,
+    pub rule_pack_version: u32,
+    pub commits_scanned: usize,
+    pub stats: ScanStats,
+    pub baseline_suppressed: usize,
+    pub findings: Vec<Finding>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rejected: Option<Vec<RejectedCandidate>>,
+}
+
+pub async fn generate_baseline(
+    query: web::Query<BaselineQuery>,
+    data: web::Data<AppState>,
+) -> ActixResult<HttpResponse> {
+    match data.state_manager.list_findings(&query.repo_url, None).await {
+        Ok(findings) => {
+            let baseline = Baseline::from_findings(&query.repo_url, &findings);
+            Ok(HttpResponse::Ok()
+                .insert_header(("Content-Disposition", format!("attachment; filename=\"{}\"", BASELINE_FILE_NAME)))
+                .json(baseline))
+        }
+        Err(e) => {
+            error!("Failed to generate baseline for {}: {}", query.repo_url, e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": e.to_string()
+            })))
+        }
+    }
+}
+
+pub async fn scan_against_baseline(
+    body: web::Json<ScanRequest>,
+    data: web::Data<AppState>,
+) -> ActixResult<HttpResponse> {
+    match run_scan(&body, &data).await {
+        Ok(report) => Ok(HttpResponse::Ok().json(report)),
+        Err(e) => {
+            error!("Scan of {} failed: {}", body.repo_url, e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": e.to_string()
+            })))
+        }
+    }
+}
+
+async fn run_scan(request: &ScanRequest, data: &web::Data<AppState>) -> anyhow::Result<ScanReport> {
+    let (provider, repo) = data.providers.resolve(&request.repo_url)?;
+    let repository = provider.get_repository(&repo).await?;
+
+    let (findings, commit_shas, stats) = scan_repository(provider.as_ref(), &repo, data).await?;
+    let scan_mode = ScanMode::from(request.scan_mode.as_deref().unwrap_or("quick"));
+    record_completed_scan(&request.repo_url, &repo, scan_mode, &commit_shas, &findings, &stats, data).await?;
+    let findings = apply_triage(&request.repo_url, findings, data).await?;
+
+    let baseline = match &request.baseline {
+        Some(baseline) => Some(baseline.clone()),
+        None => load_repo_baseline(provider.as_ref(), &repo, repository.default_branch.as_deref()).await,
+    };
+
+    let (findings, baseline_suppressed) = match baseline {
+        Some(baseline) => baseline.filter_new(findings),
+        None => (findings, 0),
+    };
+
+    info!(
+        "Scan of {} found {} new findings ({} suppressed by baseline)",
+        request.repo_url, findings.len(), baseline_suppressed
+    );
+
+    let rejected = request.debug.then(|| stats.rejected_candidates.clone());
+
+    Ok(ScanReport {
+        repo_url: request.repo_url.clone(),
+        rule_pack_version: RULE_PACK_VERSION,
+        commits_scanned: commit_shas.len(),
+        stats,
+        baseline_suppressed,
+        findings,
+        rejected,
+    })
+}
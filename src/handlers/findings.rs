@@ -0,0 +1,160 @@
+use crate::handlers::a2a::AppState;
+use crate::models::scan::FindingStatus;
+use actix_web::{web, HttpRequest, HttpResponse, Result as ActixResult};
+use actix_web::http::header::{AUTHORIZATION, WWW_AUTHENTICATE};
+use anyhow::{Result, anyhow};
+use serde::Deserialize;
+use log::{info, warn, error};
+
+// The actor recorded on a triage comes from the token, never from the request body.
+#[derive(Debug, Deserialize)]
+pub struct FindingStatusUpdate {
+    pub repo_url: String,
+    pub status: FindingStatus,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct TriageToken {
+    pub actor: String,
+    pub token: String,
+}
+
+// `TRIAGE_TOKENS` holds comma-separated `actor:token` pairs.
+pub fn parse_triage_tokens(value: &str) -> Result<Vec<TriageToken>> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let (actor, token) = entry
+                .split_once(':')
+                .map(|(actor, token)| (actor.trim(), token.trim()))
+                .filter(|(actor, token)| !actor.is_empty() && !token.is_empty())
+                .ok_or_else(|| anyhow!("Triage token entries must look like actor:token"))?;
+            Ok(TriageToken {
+                actor: actor.to_string(),
+                token: token.to_string(),
+            })
+        })
+        .collect()
+}
+
+pub(crate) fn authenticated_actor<'a>(req: &HttpRequest, tokens: &'a [TriageToken]) -> Option<&'a str> {
+    let presented = req
+        .headers()
+        .get(AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")?
+        .trim();
+
+    tokens
+        .iter()
+        .find(|t| constant_time_eq(t.token.as_bytes(), presented.as_bytes()))
+        .map(|t| t.actor.as_str())
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FindingsQuery {
+    pub repo_url: String,
+    pub status: Option<FindingStatus>,
+}
+
+pub async fn list_findings(
+    query: web::Query<FindingsQuery>,
+    data: web::Data<AppState>,
+) -> ActixResult<HttpResponse> {
+    match data.state_manager.list_findings(&query.repo_url, query.status).await {
+        Ok(findings) => Ok(HttpResponse::Ok().json(findings)),
+        Err(e) => {
+            error!("Failed to list findings for {}: {}", query.repo_url, e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": e.to_string()
+            })))
+        }
+    }
+}
+
+pub async fn update_finding_status(
+    req: HttpRequest,
+    path: web::Path<String>,
+    body: web::Json<FindingStatusUpdate>,
+    data: web::Data<AppState>,
+) -> ActixResult<HttpResponse> {
+    let fingerprint = path.into_inner();
+
+    let Some(actor) = authenticated_actor(&req, &data.triage_tokens) else {
+        warn!("Rejected unauthenticated status update for finding {}", fingerprint);
+        return Ok(HttpResponse::Unauthorized()
+            .insert_header((WWW_AUTHENTICATE, "Bearer"))
+            .json(serde_json::json!({
+                "error": "a bearer token from TRIAGE_TOKENS is required"
+            })));
+    };
+
+    if body.reason.trim().is_empty() {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "reason is required"
+        })));
+    }
+
+    match data.state_manager.set_finding_status(&body.repo_url, &fingerprint, body.status, &body.reason, actor).await {
+        Ok(finding) => {
+            info!("{} marked finding {} in {} as {:?}", actor, finding.fingerprint, body.repo_url, body.status);
+            Ok(HttpResponse::Ok().json(finding))
+        }
+        Err(e) => {
+            error!("Failed to update finding {} for {}: {}", fingerprint, body.repo_url, e);
+            Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "error": e.to_string()
+            })))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::test::TestRequest;
+
+    fn tokens() -> Vec<TriageToken> {
+        parse_triage_tokens("alice:tok-alice-123, ci-bot:tok-ci-456").unwrap()
+    }
+
+    #[test]
+    fn parses_actor_token_pairs() {
+        let tokens = tokens();
+        assert_eq!(tokens.len(), 2);
+        assert_eq!(tokens[1].actor, "ci-bot");
+        assert_eq!(tokens[1].token, "tok-ci-456");
+        assert!(parse_triage_tokens("").unwrap().is_empty());
+        assert!(parse_triage_tokens("no-separator").is_err());
+        assert!(parse_triage_tokens("alice:").is_err());
+    }
+
+    #[test]
+    fn actor_comes_from_the_bearer_token() {
+        let tokens = tokens();
+        let req = TestRequest::default().insert_header((AUTHORIZATION, "Bearer tok-ci-456")).to_http_request();
+        assert_eq!(authenticated_actor(&req, &tokens), Some("ci-bot"));
+    }
+
+    #[test]
+    fn rejects_missing_wrong_or_unconfigured_tokens() {
+        let tokens = tokens();
+        let missing = TestRequest::default().to_http_request();
+        let wrong = TestRequest::default().insert_header((AUTHORIZATION, "Bearer tok-alice-12")).to_http_request();
+        let basic = TestRequest::default().insert_header((AUTHORIZATION, "Basic tok-alice-123")).to_http_request();
+        let valid = TestRequest::default().insert_header((AUTHORIZATION, "Bearer tok-alice-123")).to_http_request();
+
+        assert_eq!(authenticated_actor(&missing, &tokens), None);
+        assert_eq!(authenticated_actor(&wrong, &tokens), None);
+        assert_eq!(authenticated_actor(&basic, &tokens), None);
+        assert_eq!(authenticated_actor(&valid, &[]), None);
+    }
+}
@@ -1 +1,3 @@
-pub mod a2a;
\ No newline at end of file
+pub mod a2a;
+pub mod baseline;
+pub mod findings;
\ No newline at end of file
@@ -3,7 +3,7 @@ use actix_cors::Cors;
 use dotenv::dotenv;
 use std::env;
 use std::sync::Arc;
-use log::info;
+use log::{info, warn};
 
 mod models;
 mod handlers;
@@ -11,45 +11,148 @@ mod services;
 mod utils;
 
 use handlers::a2a::{handle_a2a_request, AppState};
-use services::github::GitHubClient;
+use handlers::baseline::{generate_baseline, scan_against_baseline};
+use handlers::findings::{list_findings, parse_triage_tokens, update_finding_status};
+use services::github::{GitHubAuth, GitHubClient, GITHUB_API_URL};
+use services::github_app::GitHubAppAuth;
+use services::gitlab::{GitLabClient, GITLAB_URL};
+use services::provider::ProviderRegistry;
 use services::gemini::GeminiClient;
+use services::verifier::{CredentialVerifier, VerifierConfig, AWS_STS_URL, OPENAI_API_URL, SENDGRID_API_URL, STRIPE_API_URL};
 use services::state::StateManager;
 use services::scanner::SecretScanner;
+use utils::allowlist::Allowlist;
+use utils::file_filter::{FileFilter, DEFAULT_MAX_FILE_BYTES};
+use utils::logging;
+use utils::patterns::RULE_PACK_VERSION;
+use utils::redaction::{Redactor, RedactionMode};
 
 async fn health_check() -> HttpResponse {
     HttpResponse::Ok().json(serde_json::json!({
         "status": "ok",
-        "service": "secret-detector"
+        "service": "secret-detector",
+        "rule_pack_version": RULE_PACK_VERSION
     }))
 }
 
+fn env_list(name: &str) -> Vec<String> {
+    env::var(name)
+        .map(|v| v.split(',').map(|g| g.trim().to_string()).filter(|g| !g.is_empty()).collect())
+        .unwrap_or_default()
+}
+
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
     dotenv().ok();
-    env_logger::init();
+    logging::init(env::var("LOG_REQUEST_BODIES").is_ok_and(|v| v == "true" || v == "1"));
 
     let host = env::var("HOST").unwrap_or_else(|_| "0.0.0.0".to_string());
     let port = env::var("PORT").unwrap_or_else(|_| "8080".to_string());
     let github_token = env::var("GITHUB_TOKEN").ok();
+    let github_app_id = env::var("GITHUB_APP_ID").ok();
+    let github_api_url = env::var("GITHUB_API_URL").unwrap_or_else(|_| GITHUB_API_URL.to_string());
+    let github_web_url = env::var("GITHUB_WEB_URL")
+        .unwrap_or_else(|_| GitHubClient::web_url_for_api(&github_api_url));
+    let gitlab_url = env::var("GITLAB_URL").unwrap_or_else(|_| GITLAB_URL.to_string());
+    let gitlab_token = env::var("GITLAB_TOKEN").ok();
     let gemini_api_key = env::var("GEMINI_API_KEY").expect("GEMINI_API_KEY must be set");
     let gemini_model = env::var("GEMINI_MODEL").unwrap_or_else(|_| "gemini-2.0-flash-exp".to_string());
     let scan_state_file = env::var("SCAN_STATE_FILE").unwrap_or_else(|_| "scan_states.json".to_string());
+    let allowlist_file = env::var("ALLOWLIST_FILE").ok();
+    let scan_db_path = env::var("SCAN_DB_PATH").unwrap_or_else(|_| "scan_states.db".to_string());
     let max_scan_commits: u32 = env::var("MAX_SCAN_COMMITS")
         .ok()
         .and_then(|v| v.parse().ok())
         .unwrap_or(100);
+    let scan_concurrency: usize = env::var("SCAN_CONCURRENCY")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(8);
+    let org_scan_concurrency: usize = env::var("ORG_SCAN_CONCURRENCY")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(4);
+    let scan_include = env_list("SCAN_INCLUDE");
+    let scan_exclude = env_list("SCAN_EXCLUDE");
+    let max_file_bytes: usize = env::var("MAX_FILE_BYTES")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_MAX_FILE_BYTES);
+    let redaction_mode: RedactionMode = env::var("REDACTION_MODE")
+        .unwrap_or_else(|_| "partial".to_string())
+        .parse()
+        .expect("Invalid REDACTION_MODE");
+    let fingerprint_key = env::var("FINGERPRINT_KEY").ok();
+    let previous_fingerprint_keys = env_list("FINGERPRINT_KEY_PREVIOUS");
+    let verify_secrets = env::var("VERIFY_SECRETS").is_ok_and(|v| v == "true" || v == "1");
 
-    let github_client = Arc::new(GitHubClient::new(github_token).expect("Failed to create GitHub client"));
+    let github_auth = match (github_app_id, github_token) {
+        (Some(app_id), _) => {
+            let private_key = match env::var("GITHUB_APP_PRIVATE_KEY") {
+                Ok(pem) => pem.into_bytes(),
+                Err(_) => {
+                    let key_path = env::var("GITHUB_APP_PRIVATE_KEY_PATH")
+                        .expect("GITHUB_APP_PRIVATE_KEY or GITHUB_APP_PRIVATE_KEY_PATH must be set when GITHUB_APP_ID is set");
+                    std::fs::read(&key_path).expect("Failed to read GitHub App private key")
+                }
+            };
+            info!("Authenticating to GitHub as app {}", app_id);
+            let app_auth = GitHubAppAuth::new(app_id, &private_key, &github_api_url)
+                .expect("Failed to create GitHub App authenticator");
+            GitHubAuth::App(Box::new(app_auth))
+        }
+        (None, Some(token)) => GitHubAuth::Token(token),
+        (None, None) => GitHubAuth::Anonymous,
+    };
+
+    let github_client = Arc::new(GitHubClient::new(&github_api_url, &github_web_url, github_auth, scan_concurrency).expect("Failed to create GitHub client"));
+    let gitlab_client = Arc::new(GitLabClient::new(&gitlab_url, gitlab_token, scan_concurrency).expect("Failed to create GitLab client"));
+    let providers = ProviderRegistry::new(vec![github_client.clone(), gitlab_client]);
     let gemini_client = Arc::new(GeminiClient::new(gemini_api_key, gemini_model));
-    let state_manager = Arc::new(StateManager::new(&scan_state_file).expect("Failed to create state manager"));
-    let scanner = Arc::new(SecretScanner::new());
+    let state_manager = Arc::new(StateManager::new(&scan_db_path, Some(&scan_state_file)).expect("Failed to create state manager"));
+    info!("Loaded rule pack v{}", RULE_PACK_VERSION);
+    let allowlist = Allowlist::load(allowlist_file.as_deref()).expect("Failed to load allowlist");
+    let file_filter = FileFilter::new(&scan_include, &scan_exclude, max_file_bytes).expect("Invalid SCAN_INCLUDE or SCAN_EXCLUDE glob");
+    let generated_key = state_manager.generated_fingerprint_key().await.expect("Failed to load fingerprint key");
+    // Fingerprints made with the generated key stay recognisable after FINGERPRINT_KEY is set.
+    let redactor = match fingerprint_key {
+        Some(key) => Redactor::new(redaction_mode, &key, &[previous_fingerprint_keys, vec![generated_key]].concat()),
+        None => Redactor::new(redaction_mode, &generated_key, &previous_fingerprint_keys),
+    };
+    if redaction_mode == RedactionMode::Plaintext {
+        warn!("REDACTION_MODE=plaintext: raw secret values will be stored in scan state and returned in reports");
+    }
+    let scanner = Arc::new(SecretScanner::new(scan_concurrency, allowlist, file_filter, redactor));
+    let verifier = if verify_secrets {
+        let config = VerifierConfig {
+            aws_sts_url: env::var("VERIFY_AWS_STS_URL").unwrap_or_else(|_| AWS_STS_URL.to_string()),
+            stripe_url: env::var("VERIFY_STRIPE_URL").unwrap_or_else(|_| STRIPE_API_URL.to_string()),
+            sendgrid_url: env::var("VERIFY_SENDGRID_URL").unwrap_or_else(|_| SENDGRID_API_URL.to_string()),
+            openai_url: env::var("VERIFY_OPENAI_URL").unwrap_or_else(|_| OPENAI_API_URL.to_string()),
+            github_url: env::var("VERIFY_GITHUB_URL").unwrap_or_else(|_| github_api_url.clone()),
+        };
+        info!("Live credential verification enabled");
+        Some(Arc::new(CredentialVerifier::new(config).expect("Failed to create credential verifier")))
+    } else {
+        None
+    };
+
+    let triage_tokens = parse_triage_tokens(&env::var("TRIAGE_TOKENS").unwrap_or_default()).expect("Invalid TRIAGE_TOKENS");
+    if triage_tokens.is_empty() {
+        warn!("TRIAGE_TOKENS is not set: POST /findings/{{fingerprint}}/status will reject every request");
+    }
 
     let app_state = web::Data::new(AppState {
         github_client,
+        providers,
         gemini_client,
         state_manager,
         scanner,
+        verifier,
         max_scan_commits,
+        org_scan_concurrency,
+        scan_concurrency,
+        triage_tokens,
     });
 
     let bind_addr = format!("{}:{}", host, port);
@@ -64,6 +167,10 @@ async fn main() -> std::io::Result<()> {
             .app_data(app_state.clone())
             .route("/health", web::get().to(health_check))
             .route("/a2a/agent/githubScanner", web::post().to(handle_a2a_request))
+            .route("/findings", web::get().to(list_findings))
+            .route("/findings/{fingerprint}/status", web::post().to(update_finding_status))
+            .route("/baseline", web::get().to(generate_baseline))
+            .route("/scan", web::post().to(scan_against_baseline))
     })
     .bind(&bind_addr)?
     .run()
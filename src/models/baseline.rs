@@ -0,0 +1,60 @@
+use crate::models::scan::{Finding, TrackedFinding};
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use std::collections::HashSet;
+
+pub const BASELINE_VERSION: u32 = 1;
+pub const BASELINE_FILE_NAME: &str = ".secret-detector.baseline";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Baseline {
+    pub version: u32,
+    pub generated_at: DateTime<Utc>,
+    pub repo_url: String,
+    pub results: Vec<BaselineEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BaselineEntry {
+    pub fingerprint: String,
+    pub secret_type: String,
+    pub file_path: String,
+    pub line_number: usize,
+}
+
+impl Baseline {
+    pub fn from_findings(repo_url: &str, findings: &[TrackedFinding]) -> Self {
+        let mut results: Vec<BaselineEntry> = findings
+            .iter()
+            .map(|f| BaselineEntry {
+                fingerprint: f.fingerprint.clone(),
+                secret_type: f.secret_type.clone(),
+                file_path: f.file_path.clone(),
+                line_number: f.line_number,
+            })
+            .collect();
+        results.sort_by(|a, b| (&a.file_path, a.line_number).cmp(&(&b.file_path, b.line_number)));
+
+        Self {
+            version: BASELINE_VERSION,
+            generated_at: Utc::now(),
+            repo_url: repo_url.to_string(),
+            results,
+        }
+    }
+
+    pub fn filter_new(&self, findings: Vec<Finding>) -> (Vec<Finding>, usize) {
+        let known: HashSet<&str> = self.results.iter().map(|e| e.fingerprint.as_str()).collect();
+        let total = findings.len();
+        let new_findings: Vec<Finding> = findings
+            .into_iter()
+            .filter(|f| {
+                !known.contains(f.fingerprint.as_str())
+                    && !f.legacy_fingerprints.iter().any(|legacy| known.contains(legacy.as_str()))
+            })
+            .collect();
+        let suppressed = total - new_findings.len();
+
+        (new_findings, suppressed)
+    }
+}
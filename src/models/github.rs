@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
+use std::collections::HashMap;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Repository {
@@ -15,6 +16,12 @@ pub struct Repository {
     pub size: u64,
     pub stargazers_count: u32,
     pub default_branch: String,
+    #[serde(default)]
+    pub fork: bool,
+    #[serde(default)]
+    pub archived: bool,
+    #[serde(default)]
+    pub language: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -24,6 +31,13 @@ pub struct Owner {
     pub avatar_url: String,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OwnerProfile {
+    pub login: String,
+    #[serde(rename = "type")]
+    pub owner_type: String,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Commit {
     pub sha: String,
@@ -71,4 +85,47 @@ pub struct FileContent {
     pub size: u64,
     pub content: String,
     pub encoding: String,
-}
\ No newline at end of file
+}
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Issue {
+    pub number: u64,
+    pub html_url: String,
+    pub title: String,
+    pub body: Option<String>,
+    pub updated_at: DateTime<Utc>,
+    #[serde(default)]
+    pub pull_request: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IssueComment {
+    pub id: u64,
+    pub html_url: String,
+    pub body: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReviewComment {
+    pub id: u64,
+    pub html_url: String,
+    pub path: String,
+    pub body: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Gist {
+    pub id: String,
+    pub html_url: String,
+    pub files: HashMap<String, GistFile>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GistFile {
+    pub filename: String,
+    pub raw_url: String,
+    #[serde(default)]
+    pub size: u64,
+}
@@ -1,3 +1,5 @@
 pub mod a2a;
+pub mod baseline;
 pub mod github;
-pub mod scan;
\ No newline at end of file
+pub mod scan;
+pub mod source;
\ No newline at end of file
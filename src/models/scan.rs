@@ -1,5 +1,8 @@
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
+use crate::models::github::Repository;
+use std::collections::BTreeMap;
+use std::fmt;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ScanState {
@@ -12,6 +15,101 @@ pub struct ScanState {
     pub total_commits_scanned: usize,
     pub findings_count: usize,
     pub status: ScanStatus,
+    #[serde(default)]
+    pub stats: ScanStats,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SkipReason {
+    VendoredPath,
+    SkippedExtension,
+    Allowlisted,
+    Excluded,
+    NotIncluded,
+    TooLarge,
+    Binary,
+    FetchFailed,
+    Unreadable,
+    PageLimit,
+    SourceUnavailable,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RejectReason {
+    Placeholder,
+    RepeatedCharacters,
+    LowEntropy,
+    VariableReference,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RejectedCandidate {
+    pub rule_id: String,
+    pub file_path: String,
+    pub line_number: usize,
+    pub reason: RejectReason,
+    pub matched_text: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ScanStats {
+    pub files_scanned: usize,
+    pub bytes_scanned: usize,
+    pub skipped: BTreeMap<SkipReason, usize>,
+    #[serde(default)]
+    pub rejected: BTreeMap<RejectReason, usize>,
+    #[serde(skip)]
+    pub rejected_candidates: Vec<RejectedCandidate>,
+}
+
+impl ScanStats {
+    pub fn record_scanned(&mut self, bytes: usize) {
+        self.files_scanned += 1;
+        self.bytes_scanned += bytes;
+    }
+
+    pub fn record_skipped(&mut self, reason: SkipReason) {
+        *self.skipped.entry(reason).or_default() += 1;
+    }
+
+    pub fn record_rejected(&mut self, candidate: RejectedCandidate) {
+        *self.rejected.entry(candidate.reason).or_default() += 1;
+        self.rejected_candidates.push(candidate);
+    }
+
+    pub fn merge(&mut self, other: &ScanStats) {
+        self.files_scanned += other.files_scanned;
+        self.bytes_scanned += other.bytes_scanned;
+        for (reason, count) in &other.skipped {
+            *self.skipped.entry(*reason).or_default() += count;
+        }
+        for (reason, count) in &other.rejected {
+            *self.rejected.entry(*reason).or_default() += count;
+        }
+        self.rejected_candidates.extend(other.rejected_candidates.iter().cloned());
+    }
+
+    pub fn summary(&self) -> String {
+        let mut summary = format!("{} files scanned", self.files_scanned);
+
+        if !self.skipped.is_empty() {
+            let skipped: Vec<String> = self.skipped
+                .iter()
+                .map(|(reason, count)| format!("{} {:?}", count, reason))
+                .collect();
+            summary.push_str(&format!(", skipped {}", skipped.join(", ")));
+        }
+
+        if !self.rejected.is_empty() {
+            let rejected: Vec<String> = self.rejected
+                .iter()
+                .map(|(reason, count)| format!("{} {:?}", count, reason))
+                .collect();
+            summary.push_str(&format!(", rejected {}", rejected.join(", ")));
+        }
+
+        summary
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -21,6 +119,16 @@ pub enum ScanMode {
     Deep,
 }
 
+impl From<&str> for ScanMode {
+    fn from(mode: &str) -> Self {
+        match mode.to_lowercase().as_str() {
+            "running" => ScanMode::Running,
+            "deep" => ScanMode::Deep,
+            _ => ScanMode::Quick,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub enum ScanStatus {
     InProgress,
@@ -30,15 +138,209 @@ pub enum ScanStatus {
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Finding {
+    #[serde(default)]
+    pub rule_id: String,
     pub secret_type: String,
     pub severity: Severity,
     pub file_path: String,
     pub line_number: usize,
+    #[serde(default)]
+    pub end_line_number: Option<usize>,
     pub matched_text: String,
     pub commit_sha: String,
     pub commit_date: DateTime<Utc>,
     pub description: String,
     pub remediation: String,
+    #[serde(default)]
+    pub source: ContentSource,
+    #[serde(default)]
+    pub source_url: Option<String>,
+    #[serde(default)]
+    pub fingerprint: String,
+    #[serde(default)]
+    pub removed: bool,
+    #[serde(default)]
+    pub triage: Option<Triage>,
+    #[serde(default)]
+    pub verification: Option<Verification>,
+    #[serde(default)]
+    pub decoding: Vec<Encoding>,
+    #[serde(default)]
+    pub config_key: Option<String>,
+    #[serde(default)]
+    pub cell_index: Option<usize>,
+    #[serde(default)]
+    pub match_span: Option<MatchSpan>,
+    #[serde(skip)]
+    pub secret: Option<SecretValue>,
+    // Fingerprints the same secret had under earlier rule packs, so tracked findings and baselines keyed by them still match.
+    #[serde(skip)]
+    pub legacy_fingerprints: Vec<String>,
+}
+
+// 1-based character columns of the whole rule match: `start_column` on `line_number`, and the exclusive
+// `end_column` on `end_line_number` (or the same line).
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct MatchSpan {
+    pub start_column: usize,
+    pub end_column: usize,
+}
+
+#[derive(Clone, Default)]
+pub struct SecretValue(String);
+
+impl SecretValue {
+    pub fn new(value: &str) -> Self {
+        Self(value.to_string())
+    }
+
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Debug for SecretValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SecretValue(***)")
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum VerificationStatus {
+    Live,
+    Invalid,
+    Unknown,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Verification {
+    pub status: VerificationStatus,
+    pub detail: Option<String>,
+}
+
+impl Finding {
+    pub fn describe(&self) -> String {
+        let mut description = format!("{} ({:?}) in {}", self.secret_type, self.severity, self.location());
+
+        if let Some(key) = &self.config_key {
+            description.push_str(&format!(" - key {}", key));
+        }
+
+        if !self.decoding.is_empty() {
+            let chain: Vec<String> = self.decoding.iter().map(|e| format!("{:?}", e)).collect();
+            description.push_str(&format!(" - decoded from {}", chain.join(" -> ")));
+        }
+
+        if let Some(verification) = &self.verification {
+            description.push_str(&format!(" - verification: {:?}", verification.status));
+            if let Some(detail) = &verification.detail {
+                description.push_str(&format!(" ({})", detail));
+            }
+        }
+
+        description
+    }
+
+    pub fn location(&self) -> String {
+        let file_path = match self.cell_index {
+            Some(cell) => format!("{} cell {}", self.file_path, cell),
+            None => self.file_path.clone(),
+        };
+        let location = match &self.source_url {
+            Some(url) => format!("{} at {}", file_path, url),
+            None => match self.end_line_number {
+                Some(end) if end > self.line_number => {
+                    format!("{} at lines {}-{}", file_path, self.line_number, end)
+                }
+                _ => format!("{} at line {}", file_path, self.line_number),
+            },
+        };
+
+        match &self.triage {
+            Some(triage) => format!("{} [{:?} by {}: {}]", location, triage.status, triage.actor, triage.reason),
+            None => location,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    Base64,
+    Hex,
+    Percent,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ContentSource {
+    #[default]
+    Commit,
+    Issue,
+    IssueComment,
+    ReviewComment,
+    Gist,
+    Wiki,
+}
+
+#[derive(Debug, Clone)]
+pub struct SourceContent {
+    pub source: ContentSource,
+    pub label: String,
+    pub url: String,
+    pub body: String,
+    pub revision: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum FindingStatus {
+    Open,
+    Acknowledged,
+    Resolved,
+    Ignored,
+}
+
+impl FindingStatus {
+    pub fn parse(status: &str) -> Option<Self> {
+        match status.to_lowercase().replace(['-', ' '], "_").as_str() {
+            "open" | "reopen" => Some(FindingStatus::Open),
+            "acknowledged" | "acknowledge" | "ack" => Some(FindingStatus::Acknowledged),
+            "resolved" | "resolve" | "rotated" | "revoked" => Some(FindingStatus::Resolved),
+            "ignored" | "ignore" | "false_positive" => Some(FindingStatus::Ignored),
+            _ => None,
+        }
+    }
+
+    pub fn suppresses(&self) -> bool {
+        matches!(self, FindingStatus::Resolved | FindingStatus::Ignored)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Triage {
+    pub status: FindingStatus,
+    pub reason: String,
+    pub actor: String,
+    pub triaged_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TrackedFinding {
+    pub repo_url: String,
+    pub fingerprint: String,
+    pub secret_type: String,
+    pub severity: Severity,
+    pub file_path: String,
+    pub line_number: usize,
+    pub matched_text: String,
+    pub source_url: Option<String>,
+    pub first_seen: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+    pub first_commit_sha: String,
+    pub last_commit_sha: String,
+    pub status: FindingStatus,
+    pub resolved_commit_sha: Option<String>,
+    pub triage: Option<Triage>,
+    pub verification: Option<Verification>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -47,4 +349,100 @@ pub enum Severity {
     High,
     Medium,
     Low,
-}
\ No newline at end of file
+}
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct OrgScanFilter {
+    pub include_forks: bool,
+    pub include_archived: bool,
+    pub language: Option<String>,
+    pub pushed_after: Option<DateTime<Utc>>,
+}
+
+impl OrgScanFilter {
+    pub fn matches(&self, repository: &Repository) -> bool {
+        if repository.fork && !self.include_forks {
+            return false;
+        }
+
+        if repository.archived && !self.include_archived {
+            return false;
+        }
+
+        if let Some(language) = &self.language {
+            let repo_language = repository.language.as_deref().unwrap_or_default();
+            if !repo_language.eq_ignore_ascii_case(language) {
+                return false;
+            }
+        }
+
+        if let Some(pushed_after) = self.pushed_after
+            && repository.pushed_at < pushed_after
+        {
+            return false;
+        }
+
+        true
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RepoScanSummary {
+    pub repo_url: String,
+    pub commits_scanned: usize,
+    pub findings: Vec<Finding>,
+    pub error: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn repository(fork: bool, archived: bool, language: Option<&str>, pushed_at: &str) -> Repository {
+        serde_json::from_value(serde_json::json!({
+            "id": 1,
+            "name": "hello",
+            "full_name": "octocat/hello",
+            "owner": { "login": "octocat", "id": 1, "avatar_url": "" },
+            "html_url": "https://github.com/octocat/hello",
+            "description": null,
+            "created_at": "2020-01-01T00:00:00Z",
+            "updated_at": pushed_at,
+            "pushed_at": pushed_at,
+            "size": 1,
+            "stargazers_count": 0,
+            "default_branch": "main",
+            "fork": fork,
+            "archived": archived,
+            "language": language,
+        }))
+        .unwrap()
+    }
+
+    fn date(value: &str) -> Option<DateTime<Utc>> {
+        Some(DateTime::parse_from_rfc3339(value).unwrap().with_timezone(&Utc))
+    }
+
+    #[test]
+    fn default_filter_skips_forks_and_archived_repos() {
+        let filter = OrgScanFilter::default();
+        assert!(filter.matches(&repository(false, false, None, "2024-01-01T00:00:00Z")));
+        assert!(!filter.matches(&repository(true, false, None, "2024-01-01T00:00:00Z")));
+        assert!(!filter.matches(&repository(false, true, None, "2024-01-01T00:00:00Z")));
+
+        let everything = OrgScanFilter { include_forks: true, include_archived: true, ..Default::default() };
+        assert!(everything.matches(&repository(true, true, None, "2024-01-01T00:00:00Z")));
+    }
+
+    #[test]
+    fn filters_by_language_and_push_date() {
+        let rust = OrgScanFilter { language: Some("rust".to_string()), ..Default::default() };
+        assert!(rust.matches(&repository(false, false, Some("Rust"), "2024-01-01T00:00:00Z")));
+        assert!(!rust.matches(&repository(false, false, Some("Go"), "2024-01-01T00:00:00Z")));
+        assert!(!rust.matches(&repository(false, false, None, "2024-01-01T00:00:00Z")));
+
+        let recent = OrgScanFilter { pushed_after: date("2024-01-01T00:00:00Z"), ..Default::default() };
+        assert!(recent.matches(&repository(false, false, None, "2024-01-01T00:00:00Z")));
+        assert!(recent.matches(&repository(false, false, None, "2024-06-01T12:00:00Z")));
+        assert!(!recent.matches(&repository(false, false, None, "2023-12-31T23:59:59Z")));
+    }
+}
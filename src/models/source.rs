@@ -0,0 +1,55 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum ProviderKind {
+    GitHub,
+    GitLab,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct RepoRef {
+    pub owner: String,
+    pub repo: String,
+}
+
+impl RepoRef {
+    pub fn full_name(&self) -> String {
+        format!("{}/{}", self.owner, self.repo)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SourceRepository {
+    pub full_name: String,
+    pub web_url: String,
+    pub default_branch: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SourceCommit {
+    pub sha: String,
+    pub date: DateTime<Utc>,
+    pub files: Vec<SourceFile>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SourceFile {
+    pub path: String,
+    pub change: FileChange,
+    pub patch: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum FileChange {
+    Added,
+    Modified,
+    Renamed,
+    Removed,
+}
+
+impl FileChange {
+    pub fn has_content(&self) -> bool {
+        matches!(self, FileChange::Added | FileChange::Modified)
+    }
+}
@@ -1,4 +1,5 @@
-use crate::models::scan::Finding;
+use crate::models::scan::{Finding, RepoScanSummary};
+use crate::utils::logging::body_preview;
 use anyhow::{Result, anyhow};
 use serde::{Deserialize, Serialize};
 use log::{info, error};
@@ -56,6 +57,22 @@ pub struct ScanCommand {
     pub scan_mode: String,
     pub repo_url: Option<String>,
     pub action: String,
+    #[serde(default)]
+    pub include_forks: bool,
+    #[serde(default)]
+    pub include_archived: bool,
+    #[serde(default)]
+    pub language: Option<String>,
+    #[serde(default)]
+    pub pushed_after: Option<String>,
+    #[serde(default)]
+    pub include_discussions: bool,
+    #[serde(default)]
+    pub fingerprint: Option<String>,
+    #[serde(default)]
+    pub finding_status: Option<String>,
+    #[serde(default)]
+    pub reason: Option<String>,
 }
 
 pub struct GeminiClient {
@@ -131,13 +148,28 @@ Respond with this exact JSON structure:
 {{
   "scan_mode": "quick",
   "repo_url": "https://github.com/octocat/Hello-World",
-  "action": "start_scan"
+  "action": "start_scan",
+  "include_forks": false,
+  "include_archived": false,
+  "language": null,
+  "pushed_after": null,
+  "include_discussions": false,
+  "fingerprint": null,
+  "finding_status": null,
+  "reason": null
 }}
 
 Rules:
 - scan_mode: "quick", "running", or "deep"
-- repo_url: full GitHub URL or null
-- action: "start_scan", "continue_scan", "status", or "help"
+- repo_url: full GitHub, GitHub Enterprise or GitLab URL (including subgroups, e.g. https://gitlab.com/group/subgroup/project), or an SSH remote like git@github.com:owner/repo.git, exactly as the user wrote it, or null. For an organization or user, use the owner URL (e.g. https://github.com/octocat)
+- action: "start_scan", "continue_scan", "status", "update_finding", or "help". Use "status" with a repo_url when the user asks what secrets are still outstanding in a repository. Use "update_finding" when the user marks a finding as acknowledged, a false positive, rotated/resolved, or reopens it
+- fingerprint: the finding fingerprint (hex, possibly shortened) the user refers to, or null
+- finding_status: "acknowledged", "ignored" (false positive), "resolved" (rotated) or "open", or null
+- reason: the user's explanation for the status change, or null
+- include_forks / include_archived: true only if the user asks to include forked or archived repositories
+- language: primary repository language to restrict an organization scan to, or null
+- pushed_after: RFC 3339 date; only scan repositories pushed after it, or null
+- include_discussions: true if the user asks to also scan issues, pull request comments, gists or the wiki
 
 JSON only, no markdown, no explanation:"#,
             message
@@ -145,7 +177,7 @@ JSON only, no markdown, no explanation:"#,
 
         info!("Sending prompt to Gemini for intent parsing");
         let response = self.generate_content(&prompt).await?;
-        info!("Raw Gemini response: {}", response);
+        info!("Raw Gemini response: {}", body_preview(&response));
         
         let cleaned = response
             .trim()
@@ -154,7 +186,7 @@ JSON only, no markdown, no explanation:"#,
             .trim_end_matches("```")
             .trim();
 
-        info!("Cleaned response: {}", cleaned);
+        info!("Cleaned response: {}", body_preview(cleaned));
 
         match serde_json::from_str::<ScanCommand>(cleaned) {
             Ok(cmd) => {
@@ -163,7 +195,7 @@ JSON only, no markdown, no explanation:"#,
             }
             Err(e) => {
                 error!("Failed to parse Gemini response: {}", e);
-                error!("Response was: {}", cleaned);
+                error!("Response was: {}", body_preview(cleaned));
                 Err(anyhow!("Failed to parse Gemini response as JSON: {}. Response was: {}", e, cleaned))
             }
         }
@@ -176,10 +208,7 @@ JSON only, no markdown, no explanation:"#,
             findings
                 .iter()
                 .map(|f| {
-                    format!(
-                        "- {} ({:?}) in {} at line {}",
-                        f.secret_type, f.severity, f.file_path, f.line_number
-                    )
+                    format!("- {}", f.describe())
                 })
                 .collect::<Vec<_>>()
                 .join("\n")
@@ -199,7 +228,7 @@ Findings:
 
 Generate a friendly, clear response that:
 1. Summarizes what was scanned
-2. Reports findings with severity
+2. Reports findings with severity, calling out any credential verified as Live first
 3. Provides actionable recommendations
 4. Uses a conversational tone
 
@@ -211,4 +240,60 @@ Keep it concise but informative."#,
         let response = self.generate_content(&prompt).await?;
         Ok(response)
     }
-}
\ No newline at end of file
+
+    pub async fn generate_org_response(&self, summaries: &[RepoScanSummary], owner_url: &str, scan_mode: &str) -> Result<String> {
+        let total_findings: usize = summaries.iter().map(|s| s.findings.len()).sum();
+        let total_commits: usize = summaries.iter().map(|s| s.commits_scanned).sum();
+
+        let repo_summary = if summaries.is_empty() {
+            "No repositories matched the filters.".to_string()
+        } else {
+            summaries
+                .iter()
+                .map(|s| {
+                    if let Some(err) = &s.error {
+                        return format!("- {}: scan failed ({})", s.repo_url, err);
+                    }
+
+                    let mut line = format!(
+                        "- {}: {} commits scanned, {} findings",
+                        s.repo_url, s.commits_scanned, s.findings.len()
+                    );
+                    for f in &s.findings {
+                        line.push_str(&format!("\n  - {}", f.describe()));
+                    }
+                    line
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+
+        let prompt = format!(
+            r#"You are a helpful GitHub security assistant. Generate a conversational response about the results of scanning every repository of an organization or user.
+
+Scan info:
+- Owner: {}
+- Scan mode: {}
+- Repositories scanned: {}
+- Commits scanned: {}
+- Secrets found: {}
+
+Per-repository results:
+{}
+
+Generate a friendly, clear response that:
+1. Summarizes what was scanned
+2. Lists each repository with its findings and severity
+3. Highlights the repositories that need attention first
+4. Provides actionable recommendations
+5. Uses a conversational tone
+
+Keep it concise but informative."#,
+            owner_url, scan_mode, summaries.len(), total_commits, total_findings, repo_summary
+        );
+
+        info!("Generating organization scan response with Gemini");
+        let response = self.generate_content(&prompt).await?;
+        Ok(response)
+    }
+}
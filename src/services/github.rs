@@ -1,33 +1,66 @@
-use crate::models::github::{Repository, Commit, FileContent};
+use crate::models::github::{
+    Repository, Commit, FileContent, OwnerProfile, Issue, IssueComment, ReviewComment, Gist,
+};
+use crate::models::scan::{ContentSource, ScanStats, SkipReason, SourceContent};
+use crate::models::source::{FileChange, ProviderKind, RepoRef, SourceCommit, SourceFile, SourceRepository};
+use crate::services::github_app::GitHubAppAuth;
+use crate::services::provider::SourceProvider;
+use crate::services::rate_limit::RequestBudget;
+use crate::utils::logging::body_preview;
 use anyhow::{Result, anyhow};
-use reqwest::header::{HeaderMap, HeaderValue, USER_AGENT, AUTHORIZATION, ACCEPT};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use std::path::Path;
+use tokio::process::Command;
+use uuid::Uuid;
+use base64::{Engine as _, engine::general_purpose};
+use reqwest::header::{HeaderMap, HeaderValue, USER_AGENT, ACCEPT};
 use regex::Regex;
-use lazy_static::lazy_static;
 use log::{error, debug};
 
-lazy_static! {
-    static ref GITHUB_URL_REGEX: Regex = Regex::new(
-        r"github\.com/([^/]+)/([^/\s]+)"
-    ).unwrap();
+pub const GITHUB_API_URL: &str = "https://api.github.com";
+pub const GITHUB_WEB_URL: &str = "https://github.com";
+const REPOS_PER_PAGE: u32 = 100;
+const MAX_CONTENT_PAGES: u32 = 10;
+
+pub enum GitHubAuth {
+    Anonymous,
+    Token(String),
+    App(Box<GitHubAppAuth>),
 }
 
 pub struct GitHubClient {
     client: reqwest::Client,
     base_url: String,
+    web_url: String,
+    repo_url_regex: Regex,
+    owner_url_regex: Regex,
+    auth: GitHubAuth,
+    budget: RequestBudget,
 }
 
 impl GitHubClient {
-    pub fn new(token: Option<String>) -> Result<Self> {
+    pub fn new(api_url: &str, web_url: &str, auth: GitHubAuth, max_concurrent_requests: usize) -> Result<Self> {
+        let web_url = web_url.trim_end_matches('/').to_string();
+        let host = web_url
+            .trim_start_matches("https://")
+            .trim_start_matches("http://");
+        // SSH URLs name the host without the web port, and may carry their own.
+        let ssh_host = regex::escape(host.split(':').next().unwrap_or(host));
+        let host = regex::escape(host);
+
+        // Matches https://host/owner/repo[/tree/...], ssh://git@host[:port]/owner/repo.git and git@host:owner/repo.git,
+        // anchored so that a host that merely contains `host`, or has it later in the path, doesn't match.
+        let repo_url_regex = Regex::new(&format!(
+            r"^(?:(?:https?://)?{}/|ssh://git@{}(?::\d+)?/|git@{}:)([^/\s:]+)/([^/\s?#]+?)(?:\.git)?(?:[/?#]\S*)?$",
+            host, ssh_host, ssh_host
+        ))?;
+        let owner_url_regex = Regex::new(&format!(r"^(?:https?://)?{}/([^/\s]+)/?$", host))?;
+
         let mut headers = HeaderMap::new();
         headers.insert(USER_AGENT, HeaderValue::from_static("github-secret-scanner"));
         headers.insert(ACCEPT, HeaderValue::from_static("application/vnd.github+json"));
-        
-        if let Some(t) = token {
-            headers.insert(
-                AUTHORIZATION,
-                HeaderValue::from_str(&format!("Bearer {}", t))?
-            );
-        }
 
         let client = reqwest::Client::builder()
             .default_headers(headers)
@@ -35,12 +68,49 @@ impl GitHubClient {
 
         Ok(Self {
             client,
-            base_url: "https://api.github.com".to_string(),
+            base_url: api_url.trim_end_matches('/').to_string(),
+            web_url,
+            repo_url_regex,
+            owner_url_regex,
+            auth,
+            budget: RequestBudget::new(max_concurrent_requests, "x-ratelimit-"),
         })
     }
 
-    pub fn parse_repo_url(url: &str) -> Result<(String, String)> {
-        let caps = GITHUB_URL_REGEX.captures(url)
+    pub fn web_url_for_api(api_url: &str) -> String {
+        let api_url = api_url.trim_end_matches('/');
+        if api_url == GITHUB_API_URL {
+            return GITHUB_WEB_URL.to_string();
+        }
+
+        api_url.trim_end_matches("/api/v3").to_string()
+    }
+
+    async fn auth_token(&self, owner: &str) -> Result<Option<String>> {
+        match &self.auth {
+            GitHubAuth::Anonymous => Ok(None),
+            GitHubAuth::Token(token) => Ok(Some(token.clone())),
+            GitHubAuth::App(app) => app.installation_token(owner).await,
+        }
+    }
+
+    async fn get(&self, owner: &str, url: &str) -> Result<reqwest::Response> {
+        debug!("GET {}", url);
+
+        let mut request = self.client.get(url);
+        if let Some(token) = self.auth_token(owner).await? {
+            request = request.bearer_auth(token);
+        }
+
+        let _permit = self.budget.acquire().await?;
+        let response = request.send().await?;
+        self.budget.record(response.headers());
+
+        Ok(response)
+    }
+
+    pub fn parse_repo_url(&self, url: &str) -> Result<(String, String)> {
+        let caps = self.repo_url_regex.captures(url.trim())
             .ok_or_else(|| anyhow!("Invalid GitHub URL format"))?;
         
         let owner = caps.get(1)
@@ -57,11 +127,344 @@ impl GitHubClient {
         Ok((owner, repo))
     }
 
-    pub async fn get_repository(&self, owner: &str, repo: &str) -> Result<Repository> {
-        let url = format!("{}/repos/{}/{}", self.base_url, owner, repo);
-        debug!("GET {}", url);
-        
-        let response = self.client.get(&url).send().await?;
+    pub fn parse_owner_url(&self, url: &str) -> Result<String> {
+        let caps = self.owner_url_regex.captures(url.trim())
+            .ok_or_else(|| anyhow!("Invalid GitHub owner URL format"))?;
+
+        let owner = caps.get(1)
+            .ok_or_else(|| anyhow!("Could not extract owner"))?
+            .as_str()
+            .to_string();
+
+        Ok(owner)
+    }
+
+    pub async fn get_owner_profile(&self, owner: &str) -> Result<OwnerProfile> {
+        let url = format!("{}/users/{}", self.base_url, owner);
+        let response = self.get(owner, &url).await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            error!("GitHub API error {}: {}", status, error_text);
+            return Err(anyhow!("GitHub API error: {}", status));
+        }
+
+        let profile: OwnerProfile = response.json().await?;
+        Ok(profile)
+    }
+
+    pub async fn list_owner_repositories(&self, owner: &str) -> Result<Vec<Repository>> {
+        let profile = self.get_owner_profile(owner).await?;
+        let listing_url = if profile.owner_type == "Organization" {
+            format!("{}/orgs/{}/repos?type=all", self.base_url, owner)
+        } else {
+            format!("{}/users/{}/repos?type=owner", self.base_url, owner)
+        };
+
+        self.get_paginated(owner, &listing_url, u32::MAX).await
+    }
+
+    // Issues, comments and gists are capped at MAX_CONTENT_PAGES; a listing cut off there is recorded in `stats`.
+    async fn get_content_pages<T: DeserializeOwned>(&self, owner: &str, listing_url: &str, stats: &mut ScanStats) -> Result<Vec<T>> {
+        let (items, truncated) = self.get_pages(owner, listing_url, MAX_CONTENT_PAGES).await?;
+        if truncated {
+            debug!("Stopped listing {} after {} pages", listing_url, MAX_CONTENT_PAGES);
+            stats.record_skipped(SkipReason::PageLimit);
+        }
+        Ok(items)
+    }
+
+    async fn get_paginated<T: DeserializeOwned>(&self, owner: &str, listing_url: &str, max_pages: u32) -> Result<Vec<T>> {
+        Ok(self.get_pages(owner, listing_url, max_pages).await?.0)
+    }
+
+    // Also returns whether the last page read was full, i.e. whether `max_pages` may have cut the listing short.
+    async fn get_pages<T: DeserializeOwned>(&self, owner: &str, listing_url: &str, max_pages: u32) -> Result<(Vec<T>, bool)> {
+        let separator = if listing_url.contains('?') { '&' } else { '?' };
+        let mut items = Vec::new();
+        let mut page = 1;
+
+        while page <= max_pages {
+            let url = format!("{}{}per_page={}&page={}", listing_url, separator, REPOS_PER_PAGE, page);
+            let response = self.get(owner, &url).await?;
+
+            if !response.status().is_success() {
+                let status = response.status();
+                let error_text = response.text().await.unwrap_or_default();
+                error!("GitHub API error {}: {}", status, error_text);
+                return Err(anyhow!("GitHub API error: {}", status));
+            }
+
+            let batch: Vec<T> = response.json().await?;
+            let batch_len = batch.len();
+            items.extend(batch);
+
+            if batch_len < REPOS_PER_PAGE as usize {
+                return Ok((items, false));
+            }
+            page += 1;
+        }
+
+        Ok((items, true))
+    }
+
+    fn to_source_commit(commit: Commit) -> SourceCommit {
+        let files = commit.files
+            .unwrap_or_default()
+            .into_iter()
+            .map(|file| SourceFile {
+                change: match file.status.as_str() {
+                    "added" => FileChange::Added,
+                    "removed" => FileChange::Removed,
+                    "renamed" => FileChange::Renamed,
+                    _ => FileChange::Modified,
+                },
+                path: file.filename,
+                patch: file.patch,
+            })
+            .collect();
+
+        SourceCommit {
+            sha: commit.sha,
+            date: commit.commit.author.date,
+            files,
+        }
+    }
+
+    pub async fn list_issue_content(&self, owner: &str, repo: &str, stats: &mut ScanStats) -> Result<Vec<SourceContent>> {
+        let url = format!("{}/repos/{}/{}/issues?state=all", self.base_url, owner, repo);
+        let issues: Vec<Issue> = self.get_content_pages(owner, &url, stats).await?;
+
+        Ok(issues
+            .into_iter()
+            .map(|issue| {
+                let kind = if issue.pull_request.is_some() { "pull request" } else { "issue" };
+                SourceContent {
+                    source: ContentSource::Issue,
+                    label: format!("{} #{}", kind, issue.number),
+                    url: issue.html_url,
+                    body: format!("{}\n{}", issue.title, issue.body.unwrap_or_default()),
+                    revision: String::new(),
+                    updated_at: issue.updated_at,
+                }
+            })
+            .collect())
+    }
+
+    pub async fn list_issue_comment_content(&self, owner: &str, repo: &str, stats: &mut ScanStats) -> Result<Vec<SourceContent>> {
+        let url = format!("{}/repos/{}/{}/issues/comments", self.base_url, owner, repo);
+        let comments: Vec<IssueComment> = self.get_content_pages(owner, &url, stats).await?;
+
+        Ok(comments
+            .into_iter()
+            .filter_map(|comment| {
+                Some(SourceContent {
+                    source: ContentSource::IssueComment,
+                    label: format!("comment {}", comment.id),
+                    url: comment.html_url,
+                    body: comment.body?,
+                    revision: String::new(),
+                    updated_at: comment.updated_at,
+                })
+            })
+            .collect())
+    }
+
+    pub async fn list_review_comment_content(&self, owner: &str, repo: &str, stats: &mut ScanStats) -> Result<Vec<SourceContent>> {
+        let url = format!("{}/repos/{}/{}/pulls/comments", self.base_url, owner, repo);
+        let comments: Vec<ReviewComment> = self.get_content_pages(owner, &url, stats).await?;
+
+        Ok(comments
+            .into_iter()
+            .filter_map(|comment| {
+                Some(SourceContent {
+                    source: ContentSource::ReviewComment,
+                    label: format!("review comment on {}", comment.path),
+                    url: comment.html_url,
+                    body: comment.body?,
+                    revision: String::new(),
+                    updated_at: comment.updated_at,
+                })
+            })
+            .collect())
+    }
+
+    // Gist files go through the same request budget as API calls, and files over `max_file_bytes` are skipped
+    // without being read in full. A file that can't be fetched is recorded in `stats` rather than failing the listing.
+    pub async fn list_gist_content(&self, owner: &str, max_file_bytes: usize, stats: &mut ScanStats) -> Result<Vec<SourceContent>> {
+        let url = format!("{}/users/{}/gists", self.base_url, owner);
+        let gists: Vec<Gist> = self.get_content_pages(owner, &url, stats).await?;
+
+        let mut contents = Vec::new();
+        for gist in gists {
+            for file in gist.files.into_values() {
+                if file.size as usize > max_file_bytes {
+                    stats.record_skipped(SkipReason::TooLarge);
+                    continue;
+                }
+
+                let body = match self.get_capped(owner, &file.raw_url, max_file_bytes).await {
+                    Ok(Some(body)) => body,
+                    Ok(None) => {
+                        stats.record_skipped(SkipReason::TooLarge);
+                        continue;
+                    }
+                    Err(e) => {
+                        debug!("Could not fetch gist file {}: {}", file.filename, e);
+                        stats.record_skipped(SkipReason::FetchFailed);
+                        continue;
+                    }
+                };
+
+                contents.push(SourceContent {
+                    source: ContentSource::Gist,
+                    label: format!("gist {} ({})", gist.id, file.filename),
+                    url: gist.html_url.clone(),
+                    body: String::from_utf8_lossy(&body).into_owned(),
+                    revision: gist.id.clone(),
+                    updated_at: gist.updated_at,
+                });
+            }
+        }
+
+        Ok(contents)
+    }
+
+    // Returns None once the body grows past `max_bytes`, without reading the rest of it.
+    async fn get_capped(&self, owner: &str, url: &str, max_bytes: usize) -> Result<Option<Vec<u8>>> {
+        let mut response = self.get(owner, url).await?;
+        if !response.status().is_success() {
+            return Err(anyhow!("GitHub error: {}", response.status()));
+        }
+        if response.content_length().is_some_and(|len| len as usize > max_bytes) {
+            return Ok(None);
+        }
+
+        let mut body = Vec::new();
+        while let Some(chunk) = response.chunk().await? {
+            if body.len() + chunk.len() > max_bytes {
+                return Ok(None);
+            }
+            body.extend_from_slice(&chunk);
+        }
+
+        Ok(Some(body))
+    }
+
+    pub async fn list_wiki_content(&self, owner: &str, repo: &str) -> Result<Vec<SourceContent>> {
+        let wiki_url = format!("{}/{}/{}.wiki.git", self.web_url, owner, repo);
+        let checkout_dir = std::env::temp_dir().join(format!("secret-detector-wiki-{}", Uuid::new_v4()));
+
+        let mut clone = Command::new("git");
+        // Passed through the environment rather than `-c`, so the token doesn't show up in `ps` or /proc/*/cmdline.
+        if let Some(token) = self.auth_token(owner).await? {
+            let credentials = general_purpose::STANDARD.encode(format!("x-access-token:{}", token));
+            clone
+                .env("GIT_CONFIG_COUNT", "1")
+                .env("GIT_CONFIG_KEY_0", "http.extraheader")
+                .env("GIT_CONFIG_VALUE_0", format!("AUTHORIZATION: basic {}", credentials));
+        }
+
+        let output = clone
+            .args(["clone", "--quiet", "--depth", "1", &wiki_url])
+            .arg(&checkout_dir)
+            .env("GIT_TERMINAL_PROMPT", "0")
+            .output()
+            .await?;
+
+        if !output.status.success() {
+            debug!("No wiki available for {}/{}: {}", owner, repo, String::from_utf8_lossy(&output.stderr).trim());
+            let _ = tokio::fs::remove_dir_all(&checkout_dir).await;
+            return Ok(Vec::new());
+        }
+
+        let result = self.read_wiki_checkout(&checkout_dir, owner, repo).await;
+        let _ = tokio::fs::remove_dir_all(&checkout_dir).await;
+        result
+    }
+
+    async fn read_wiki_checkout(&self, checkout_dir: &Path, owner: &str, repo: &str) -> Result<Vec<SourceContent>> {
+        let head = Command::new("git")
+            .args(["log", "-1", "--format=%H%n%cI"])
+            .current_dir(checkout_dir)
+            .output()
+            .await?;
+        let head = String::from_utf8_lossy(&head.stdout).to_string();
+        let mut head = head.lines();
+        let revision = head.next().unwrap_or_default().trim().to_string();
+        let updated_at = head
+            .next()
+            .and_then(|date| DateTime::parse_from_rfc3339(date.trim()).ok())
+            .map(|date| date.with_timezone(&Utc))
+            .ok_or_else(|| anyhow!("Could not read the wiki HEAD commit date"))?;
+
+        let dir = checkout_dir.to_path_buf();
+        let pages = tokio::task::spawn_blocking(move || read_wiki_pages(&dir)).await??;
+
+        Ok(pages
+            .into_iter()
+            .map(|(relative, page, body)| SourceContent {
+                source: ContentSource::Wiki,
+                label: format!("wiki {}", relative),
+                url: format!("{}/{}/{}/wiki/{}", self.web_url, owner, repo, page),
+                body,
+                revision: revision.clone(),
+                updated_at,
+            })
+            .collect())
+    }
+}
+
+// Returns (path relative to the checkout, page name, text) for every readable file outside `.git`. Symlinks are
+// skipped: the wiki is controlled by the repo owner, and a link to `/` or `/proc/self/environ` must not be followed.
+fn read_wiki_pages(checkout_dir: &Path) -> Result<Vec<(String, String, String)>> {
+    let mut pages = Vec::new();
+    let mut pending = vec![checkout_dir.to_path_buf()];
+
+    while let Some(dir) = pending.pop() {
+        for entry in std::fs::read_dir(&dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            if path.file_name().is_some_and(|name| name == ".git") {
+                continue;
+            }
+            let file_type = entry.file_type()?;
+            if file_type.is_dir() {
+                pending.push(path);
+                continue;
+            }
+            if !file_type.is_file() {
+                continue;
+            }
+
+            let Ok(body) = std::fs::read_to_string(&path) else {
+                continue;
+            };
+            let relative = path.strip_prefix(checkout_dir)?.to_string_lossy().to_string();
+            let page = path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+            pages.push((relative, page, body));
+        }
+    }
+
+    Ok(pages)
+}
+
+#[async_trait]
+impl SourceProvider for GitHubClient {
+    fn kind(&self) -> ProviderKind {
+        ProviderKind::GitHub
+    }
+
+    fn parse_repo_url(&self, url: &str) -> Option<RepoRef> {
+        GitHubClient::parse_repo_url(self, url)
+            .ok()
+            .map(|(owner, repo)| RepoRef { owner, repo })
+    }
+
+    async fn get_repository(&self, repo: &RepoRef) -> Result<SourceRepository> {
+        let url = format!("{}/repos/{}/{}", self.base_url, repo.owner, repo.repo);
+        let response = self.get(&repo.owner, &url).await?;
         
         if !response.status().is_success() {
             let status = response.status();
@@ -71,27 +474,29 @@ impl GitHubClient {
         }
 
         let repository: Repository = response.json().await?;
-        Ok(repository)
+        Ok(SourceRepository {
+            full_name: repository.full_name,
+            web_url: repository.html_url,
+            default_branch: Some(repository.default_branch),
+        })
     }
 
-    pub async fn list_commits(
+    async fn list_commits(
         &self,
-        owner: &str,
-        repo: &str,
+        repo: &RepoRef,
         since: Option<&str>,
         per_page: u32,
-    ) -> Result<Vec<Commit>> {
+    ) -> Result<Vec<SourceCommit>> {
         let mut url = format!(
             "{}/repos/{}/{}/commits?per_page={}",
-            self.base_url, owner, repo, per_page
+            self.base_url, repo.owner, repo.repo, per_page
         );
 
         if let Some(since_date) = since {
             url.push_str(&format!("&since={}", since_date));
         }
 
-        debug!("GET {}", url);
-        let response = self.client.get(&url).send().await?;
+        let response = self.get(&repo.owner, &url).await?;
         
         if !response.status().is_success() {
             let status = response.status();
@@ -101,14 +506,12 @@ impl GitHubClient {
         }
 
         let commits: Vec<Commit> = response.json().await?;
-        Ok(commits)
+        Ok(commits.into_iter().map(Self::to_source_commit).collect())
     }
 
-    pub async fn get_commit(&self, owner: &str, repo: &str, sha: &str) -> Result<Commit> {
-        let url = format!("{}/repos/{}/{}/commits/{}", self.base_url, owner, repo, sha);
-        debug!("GET {}", url);
-        
-        let response = self.client.get(&url).send().await?;
+    async fn get_commit(&self, repo: &RepoRef, sha: &str) -> Result<SourceCommit> {
+        let url = format!("{}/repos/{}/{}/commits/{}", self.base_url, repo.owner, repo.repo, sha);
+        let response = self.get(&repo.owner, &url).await?;
         
         if !response.status().is_success() {
             let status = response.status();
@@ -121,29 +524,26 @@ impl GitHubClient {
         debug!("Raw response length: {} bytes", response_text.len());
         
         match serde_json::from_str::<Commit>(&response_text) {
-            Ok(commit) => Ok(commit),
+            Ok(commit) => Ok(Self::to_source_commit(commit)),
             Err(e) => {
                 error!("Failed to parse commit JSON: {}", e);
-                error!("Response preview: {}", &response_text[..response_text.len().min(500)]);
+                error!("Response preview: {}", body_preview(&response_text));
                 Err(anyhow!("Failed to parse GitHub commit response: {}", e))
             }
         }
     }
 
-    pub async fn get_file_content(
+    async fn get_file_content(
         &self,
-        owner: &str,
-        repo: &str,
+        repo: &RepoRef,
         path: &str,
         ref_sha: &str,
-    ) -> Result<FileContent> {
+    ) -> Result<Vec<u8>> {
         let url = format!(
             "{}/repos/{}/{}/contents/{}?ref={}",
-            self.base_url, owner, repo, path, ref_sha
+            self.base_url, repo.owner, repo.repo, path, ref_sha
         );
-        debug!("GET {}", url);
-        
-        let response = self.client.get(&url).send().await?;
+        let response = self.get(&repo.owner, &url).await?;
         
         if !response.status().is_success() {
             let status = response.status();
@@ -152,7 +552,225 @@ impl GitHubClient {
             return Err(anyhow!("GitHub API error: {}", status));
         }
 
-        let content: FileContent = response.json().await?;
-        Ok(content)
+        let file_content: FileContent = response.json().await?;
+        let cleaned_content = file_content.content.replace("\n", "").replace("\r", "");
+        debug!("Original content length: {}, cleaned: {}", file_content.content.len(), cleaned_content.len());
+
+        general_purpose::STANDARD.decode(&cleaned_content)
+            .map_err(|e| anyhow!("Failed to decode base64 content for {}: {}", path, e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::{Matcher, Server};
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = std::process::Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .env("GIT_AUTHOR_NAME", "test")
+            .env("GIT_AUTHOR_EMAIL", "test@example.com")
+            .env("GIT_COMMITTER_NAME", "test")
+            .env("GIT_COMMITTER_EMAIL", "test@example.com")
+            .env("GIT_AUTHOR_DATE", "2023-05-06T07:08:09+00:00")
+            .env("GIT_COMMITTER_DATE", "2023-05-06T07:08:09+00:00")
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {:?} failed", args);
+    }
+
+    fn parse_repo(web_url: &str, url: &str) -> Option<(String, String)> {
+        let client = GitHubClient::new(GITHUB_API_URL, web_url, GitHubAuth::Anonymous, 1).unwrap();
+        client.parse_repo_url(url).ok()
+    }
+
+    fn repo(owner: &str, repo: &str) -> Option<(String, String)> {
+        Some((owner.to_string(), repo.to_string()))
+    }
+
+    #[test]
+    fn parses_github_repo_urls() {
+        let cases = [
+            ("https://github.com/octocat/hello", repo("octocat", "hello")),
+            ("https://github.com/octocat/hello/", repo("octocat", "hello")),
+            ("https://github.com/octocat/hello.git", repo("octocat", "hello")),
+            ("https://github.com/octocat/hello/tree/main/src", repo("octocat", "hello")),
+            ("https://github.com/octocat/hello?tab=readme", repo("octocat", "hello")),
+            ("github.com/octocat/hello", repo("octocat", "hello")),
+            ("git@github.com:octocat/hello.git", repo("octocat", "hello")),
+            ("git@github.com:octocat/hello", repo("octocat", "hello")),
+            ("ssh://git@github.com/octocat/hello.git", repo("octocat", "hello")),
+            ("ssh://git@github.com:22/octocat/hello.git", repo("octocat", "hello")),
+            ("  https://github.com/octocat/hello  ", repo("octocat", "hello")),
+            ("https://github.com/octocat", None),
+            ("https://mygithub.com/octocat/hello", None),
+            ("https://github.com.evil.io/octocat/hello", None),
+            ("https://evil.io/github.com/octocat/hello", None),
+            ("git@evil.io:github.com/hello", None),
+        ];
+
+        for (url, expected) in cases {
+            assert_eq!(parse_repo(GITHUB_WEB_URL, url), expected, "{}", url);
+        }
+    }
+
+    #[test]
+    fn parses_enterprise_server_repo_urls() {
+        let web_url = "https://ghe.example.com:8443";
+        assert_eq!(parse_repo(web_url, "https://ghe.example.com:8443/org/repo"), repo("org", "repo"));
+        assert_eq!(parse_repo(web_url, "git@ghe.example.com:org/repo.git"), repo("org", "repo"));
+        assert_eq!(parse_repo(web_url, "ssh://git@ghe.example.com:2222/org/repo.git"), repo("org", "repo"));
+        assert_eq!(parse_repo(web_url, "ssh://git@ghe.example.com/org/repo"), repo("org", "repo"));
+        assert_eq!(parse_repo(web_url, "https://github.com/org/repo"), None);
+        assert_eq!(parse_repo(web_url, "https://ghe.example.com/org/repo"), None);
+    }
+
+    #[test]
+    fn parses_owner_urls() {
+        let client = GitHubClient::new(GITHUB_API_URL, GITHUB_WEB_URL, GitHubAuth::Anonymous, 1).unwrap();
+        let owner = |url: &str| client.parse_owner_url(url).ok();
+
+        assert_eq!(owner("https://github.com/octocat").as_deref(), Some("octocat"));
+        assert_eq!(owner("https://github.com/octocat/").as_deref(), Some("octocat"));
+        assert_eq!(owner("github.com/octo-org").as_deref(), Some("octo-org"));
+        assert_eq!(owner(" https://github.com/octocat ").as_deref(), Some("octocat"));
+        assert_eq!(owner("https://github.com/octocat/hello"), None);
+        assert_eq!(owner("https://github.com.evil.io/octocat"), None);
+        assert_eq!(owner("https://evil.io/github.com/octocat"), None);
+
+        let enterprise = GitHubClient::new(GITHUB_API_URL, "https://ghe.example.com:8443", GitHubAuth::Anonymous, 1).unwrap();
+        assert_eq!(enterprise.parse_owner_url("https://ghe.example.com:8443/platform").ok().as_deref(), Some("platform"));
+        assert!(enterprise.parse_owner_url("https://github.com/platform").is_err());
+    }
+
+    #[tokio::test]
+    async fn wiki_pages_carry_head_commit_date() {
+        let root = std::env::temp_dir().join(format!("secret-detector-wiki-test-{}", Uuid::new_v4()));
+        let wiki = root.join("octocat").join("hello.wiki.git");
+        std::fs::create_dir_all(wiki.join("guides")).unwrap();
+        std::fs::write(wiki.join("Home.md"), "Welcome").unwrap();
+        std::fs::write(wiki.join("guides").join("Deploy.md"), "token: abc").unwrap();
+        git(&wiki, &["init", "--quiet"]);
+        git(&wiki, &["add", "."]);
+        git(&wiki, &["commit", "--quiet", "-m", "pages"]);
+
+        let web_url = format!("file://{}", root.display());
+        let client = GitHubClient::new(GITHUB_API_URL, &web_url, GitHubAuth::Anonymous, 1).unwrap();
+        let mut pages = client.list_wiki_content("octocat", "hello").await.unwrap();
+        std::fs::remove_dir_all(&root).unwrap();
+
+        pages.sort_by(|a, b| a.label.cmp(&b.label));
+        let labels: Vec<&str> = pages.iter().map(|p| p.label.as_str()).collect();
+        assert_eq!(labels, vec!["wiki Home.md", "wiki guides/Deploy.md"]);
+        assert!(pages.iter().all(|p| p.updated_at.to_rfc3339() == "2023-05-06T07:08:09+00:00"));
+        assert!(pages.iter().all(|p| p.revision.len() == 40));
+        assert!(pages[1].url.ends_with("/octocat/hello/wiki/Deploy"));
+    }
+
+    #[test]
+    fn wiki_walk_skips_symlinks() {
+        let root = std::env::temp_dir().join(format!("secret-detector-wiki-links-{}", Uuid::new_v4()));
+        let outside = root.join("outside");
+        let checkout = root.join("checkout");
+        std::fs::create_dir_all(outside.join("nested")).unwrap();
+        std::fs::create_dir_all(&checkout).unwrap();
+        std::fs::write(outside.join("environ"), "GITHUB_TOKEN=secret").unwrap();
+        std::fs::write(outside.join("nested").join("Page.md"), "outside the checkout").unwrap();
+        std::fs::write(checkout.join("Home.md"), "Welcome").unwrap();
+        std::os::unix::fs::symlink(outside.join("environ"), checkout.join("Environ.md")).unwrap();
+        std::os::unix::fs::symlink(&outside, checkout.join("linked")).unwrap();
+        std::os::unix::fs::symlink(&checkout, checkout.join("loop")).unwrap();
+
+        let pages = read_wiki_pages(&checkout).unwrap();
+        std::fs::remove_dir_all(&root).unwrap();
+
+        let labels: Vec<&str> = pages.iter().map(|(relative, _, _)| relative.as_str()).collect();
+        assert_eq!(labels, vec!["Home.md"]);
+    }
+
+    #[tokio::test]
+    async fn gist_files_are_capped_and_failures_recorded() {
+        let mut server = Server::new_async().await;
+        let gists = serde_json::json!([{
+            "id": "g1",
+            "html_url": "https://gist.github.com/g1",
+            "updated_at": "2024-01-01T00:00:00Z",
+            "files": {
+                "small.env": { "filename": "small.env", "raw_url": format!("{}/raw/small", server.url()), "size": 12 },
+                "huge.log": { "filename": "huge.log", "raw_url": format!("{}/raw/huge", server.url()), "size": 10_000 },
+                "lying.txt": { "filename": "lying.txt", "raw_url": format!("{}/raw/lying", server.url()), "size": 1 },
+                "gone.txt": { "filename": "gone.txt", "raw_url": format!("{}/raw/gone", server.url()), "size": 1 }
+            }
+        }]);
+        let _list = server
+            .mock("GET", "/users/octocat/gists")
+            .match_query(Matcher::Any)
+            .with_body(gists.to_string())
+            .create_async()
+            .await;
+        let _small = server.mock("GET", "/raw/small").with_body("API_KEY=abc1").create_async().await;
+        let huge = server.mock("GET", "/raw/huge").expect(0).create_async().await;
+        let _lying = server.mock("GET", "/raw/lying").with_body("x".repeat(200)).create_async().await;
+        let _gone = server.mock("GET", "/raw/gone").with_status(500).create_async().await;
+
+        let client = GitHubClient::new(&server.url(), GITHUB_WEB_URL, GitHubAuth::Anonymous, 2).unwrap();
+        let mut stats = ScanStats::default();
+        let contents = client.list_gist_content("octocat", 100, &mut stats).await.unwrap();
+
+        assert_eq!(contents.len(), 1);
+        assert_eq!(contents[0].body, "API_KEY=abc1");
+        assert_eq!(stats.skipped[&SkipReason::TooLarge], 2);
+        assert_eq!(stats.skipped[&SkipReason::FetchFailed], 1);
+        huge.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn content_listing_cut_off_at_the_page_limit_is_recorded() {
+        let mut server = Server::new_async().await;
+        let comment = serde_json::json!({
+            "id": 1, "html_url": "https://github.com/o/r/issues/1#c1", "body": "hi", "updated_at": "2024-01-01T00:00:00Z"
+        });
+        let page = serde_json::Value::Array(vec![comment; REPOS_PER_PAGE as usize]);
+        let listing = server
+            .mock("GET", "/repos/o/r/issues/comments")
+            .match_query(Matcher::Any)
+            .with_body(page.to_string())
+            .expect(MAX_CONTENT_PAGES as usize)
+            .create_async()
+            .await;
+
+        let client = GitHubClient::new(&server.url(), GITHUB_WEB_URL, GitHubAuth::Anonymous, 2).unwrap();
+        let mut stats = ScanStats::default();
+        let contents = client.list_issue_comment_content("o", "r", &mut stats).await.unwrap();
+
+        listing.assert_async().await;
+        assert_eq!(contents.len(), (REPOS_PER_PAGE * MAX_CONTENT_PAGES) as usize);
+        assert_eq!(stats.skipped[&SkipReason::PageLimit], 1);
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn app_auth_falls_back_to_anonymous_for_owners_without_an_installation() {
+        let mut server = Server::new_async().await;
+        server
+            .mock("GET", "/app/installations")
+            .match_query(Matcher::Any)
+            .with_body("[]")
+            .create_async()
+            .await;
+        let profile = server
+            .mock("GET", "/users/octocat")
+            .match_header("authorization", Matcher::Missing)
+            .with_body(r#"{"login": "octocat", "type": "User"}"#)
+            .create_async()
+            .await;
+
+        let key = include_bytes!("../../tests/fixtures/github_app_test_key.pem");
+        let app = GitHubAppAuth::new("12345".to_string(), key, &server.url()).unwrap();
+        let client = GitHubClient::new(&server.url(), GITHUB_WEB_URL, GitHubAuth::App(Box::new(app)), 2).unwrap();
+
+        assert_eq!(client.get_owner_profile("octocat").await.unwrap().login, "octocat");
+        profile.assert_async().await;
+    }
+}
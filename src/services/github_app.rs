@@ -0,0 +1,352 @@
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Duration, Utc};
+use jsonwebtoken::{Algorithm, EncodingKey, Header};
+use log::{debug, error, info};
+use reqwest::header::{HeaderMap, HeaderValue, USER_AGENT, ACCEPT, AUTHORIZATION};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tokio::sync::RwLock;
+
+const JWT_LIFETIME_SECS: i64 = 9 * 60;
+const JWT_CLOCK_SKEW_SECS: i64 = 60;
+const TOKEN_REFRESH_MARGIN_SECS: i64 = 5 * 60;
+const INSTALLATIONS_PER_PAGE: u32 = 100;
+const MAX_INSTALLATION_PAGES: u32 = 100;
+const MISSING_INSTALLATION_TTL_SECS: i64 = 10 * 60;
+
+#[derive(Debug, Serialize)]
+struct AppClaims {
+    iat: i64,
+    exp: i64,
+    iss: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Installation {
+    id: u64,
+    account: InstallationAccount,
+}
+
+#[derive(Debug, Deserialize)]
+struct InstallationAccount {
+    login: String,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+struct InstallationToken {
+    token: String,
+    expires_at: DateTime<Utc>,
+}
+
+pub struct GitHubAppAuth {
+    client: reqwest::Client,
+    base_url: String,
+    app_id: String,
+    private_key: EncodingKey,
+    installations: RwLock<HashMap<String, u64>>,
+    // Owners without an installation, with when they were last looked up.
+    missing_installations: RwLock<HashMap<String, DateTime<Utc>>>,
+    tokens: RwLock<HashMap<u64, InstallationToken>>,
+}
+
+impl GitHubAppAuth {
+    pub fn new(app_id: String, private_key_pem: &[u8], base_url: &str) -> Result<Self> {
+        let mut headers = HeaderMap::new();
+        headers.insert(USER_AGENT, HeaderValue::from_static("github-secret-scanner"));
+        headers.insert(ACCEPT, HeaderValue::from_static("application/vnd.github+json"));
+
+        let client = reqwest::Client::builder()
+            .default_headers(headers)
+            .build()?;
+
+        let private_key = EncodingKey::from_rsa_pem(private_key_pem)
+            .map_err(|e| anyhow!("Invalid GitHub App private key: {}", e))?;
+
+        Ok(Self {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            app_id,
+            private_key,
+            installations: RwLock::new(HashMap::new()),
+            missing_installations: RwLock::new(HashMap::new()),
+            tokens: RwLock::new(HashMap::new()),
+        })
+    }
+
+    // Returns None when the app is not installed for the owner, so callers fall
+    // back to anonymous access and can still read the owner's public repos.
+    pub async fn installation_token(&self, owner: &str) -> Result<Option<String>> {
+        let Some(installation_id) = self.installation_id(owner).await? else {
+            debug!("GitHub App is not installed for {}, using anonymous access", owner);
+            return Ok(None);
+        };
+
+        if let Some(token) = self.tokens.read().await.get(&installation_id)
+            && token.expires_at - Utc::now() > Duration::seconds(TOKEN_REFRESH_MARGIN_SECS)
+        {
+            return Ok(Some(token.token.clone()));
+        }
+
+        // No lock is held across the exchange, so refreshes for different
+        // installations don't wait on each other.
+        let url = format!("{}/app/installations/{}/access_tokens", self.base_url, installation_id);
+        debug!("POST {}", url);
+
+        let response = self.client
+            .post(&url)
+            .header(AUTHORIZATION, format!("Bearer {}", self.app_jwt()?))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            error!("GitHub App token exchange error {}: {}", status, error_text);
+            return Err(anyhow!("GitHub App token exchange error: {}", status));
+        }
+
+        let token: InstallationToken = response.json().await?;
+        info!("Minted installation token for {} (expires {})", owner, token.expires_at);
+        self.tokens.write().await.insert(installation_id, token.clone());
+
+        Ok(Some(token.token))
+    }
+
+    async fn installation_id(&self, owner: &str) -> Result<Option<u64>> {
+        let key = owner.to_lowercase();
+
+        if let Some(id) = self.installations.read().await.get(&key) {
+            return Ok(Some(*id));
+        }
+        if let Some(checked_at) = self.missing_installations.read().await.get(&key)
+            && Utc::now() - *checked_at < Duration::seconds(MISSING_INSTALLATION_TTL_SECS)
+        {
+            return Ok(None);
+        }
+
+        let mut page = 1;
+        while page <= MAX_INSTALLATION_PAGES {
+            let url = format!("{}/app/installations?per_page={}&page={}", self.base_url, INSTALLATIONS_PER_PAGE, page);
+            debug!("GET {}", url);
+
+            let response = self.client
+                .get(&url)
+                .header(AUTHORIZATION, format!("Bearer {}", self.app_jwt()?))
+                .send()
+                .await?;
+
+            if !response.status().is_success() {
+                let status = response.status();
+                let error_text = response.text().await.unwrap_or_default();
+                error!("GitHub App installations error {}: {}", status, error_text);
+                return Err(anyhow!("GitHub App installations error: {}", status));
+            }
+
+            let installations: Vec<Installation> = response.json().await?;
+            let batch_len = installations.len();
+            let mut cache = self.installations.write().await;
+            for installation in installations {
+                cache.insert(installation.account.login.to_lowercase(), installation.id);
+            }
+
+            if let Some(id) = cache.get(&key) {
+                return Ok(Some(*id));
+            }
+            if batch_len < INSTALLATIONS_PER_PAGE as usize {
+                break;
+            }
+            page += 1;
+        }
+
+        self.missing_installations.write().await.insert(key, Utc::now());
+        Ok(None)
+    }
+
+    fn app_jwt(&self) -> Result<String> {
+        let now = Utc::now().timestamp();
+        let claims = AppClaims {
+            iat: now - JWT_CLOCK_SKEW_SECS,
+            exp: now + JWT_LIFETIME_SECS,
+            iss: self.app_id.clone(),
+        };
+
+        jsonwebtoken::encode(&Header::new(Algorithm::RS256), &claims, &self.private_key)
+            .map_err(|e| anyhow!("Failed to sign GitHub App JWT: {}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::{Matcher, Server};
+
+    const TEST_KEY: &[u8] = include_bytes!("../../tests/fixtures/github_app_test_key.pem");
+
+    fn installations_page(first_id: u64, count: usize) -> String {
+        let installations: Vec<serde_json::Value> = (0..count as u64)
+            .map(|i| serde_json::json!({ "id": first_id + i, "account": { "login": format!("org-{}", first_id + i) } }))
+            .collect();
+        serde_json::Value::Array(installations).to_string()
+    }
+
+    fn token_body(token: &str, expires_in: Duration) -> String {
+        serde_json::json!({ "token": token, "expires_at": Utc::now() + expires_in }).to_string()
+    }
+
+    fn auth(server: &Server) -> GitHubAppAuth {
+        GitHubAppAuth::new("12345".to_string(), TEST_KEY, &server.url()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn exchanges_app_jwt_for_installation_token() {
+        let mut server = Server::new_async().await;
+        let installations = server
+            .mock("GET", "/app/installations")
+            .match_query(Matcher::Any)
+            .match_header("authorization", Matcher::Regex(r"^Bearer [\w-]+\.[\w-]+\.[\w-]+$".to_string()))
+            .with_body(r#"[{"id": 7, "account": {"login": "Octo-Org"}}]"#)
+            .create_async()
+            .await;
+        let exchange = server
+            .mock("POST", "/app/installations/7/access_tokens")
+            .match_header("authorization", Matcher::Regex(r"^Bearer [\w-]+\.[\w-]+\.[\w-]+$".to_string()))
+            .with_status(201)
+            .with_body(token_body("ghs_first", Duration::hours(1)))
+            .expect(1)
+            .create_async()
+            .await;
+
+        let auth = auth(&server);
+        assert_eq!(auth.installation_token("octo-org").await.unwrap().as_deref(), Some("ghs_first"));
+        // Still valid well beyond the refresh margin, so served from the cache.
+        assert_eq!(auth.installation_token("octo-org").await.unwrap().as_deref(), Some("ghs_first"));
+
+        installations.assert_async().await;
+        exchange.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn refreshes_token_close_to_expiry() {
+        let mut server = Server::new_async().await;
+        server
+            .mock("GET", "/app/installations")
+            .match_query(Matcher::Any)
+            .with_body(r#"[{"id": 7, "account": {"login": "octo-org"}}]"#)
+            .create_async()
+            .await;
+        let exchange = server
+            .mock("POST", "/app/installations/7/access_tokens")
+            .with_status(201)
+            .with_body(token_body("ghs_expiring", Duration::minutes(2)))
+            .expect(2)
+            .create_async()
+            .await;
+
+        let auth = auth(&server);
+        auth.installation_token("octo-org").await.unwrap();
+        auth.installation_token("octo-org").await.unwrap();
+
+        exchange.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn picks_installation_for_owner_across_pages() {
+        let mut server = Server::new_async().await;
+        server
+            .mock("GET", "/app/installations")
+            .match_query(Matcher::UrlEncoded("page".to_string(), "1".to_string()))
+            .with_body(installations_page(1, INSTALLATIONS_PER_PAGE as usize))
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/app/installations")
+            .match_query(Matcher::UrlEncoded("page".to_string(), "2".to_string()))
+            .with_body(installations_page(101, 2))
+            .create_async()
+            .await;
+        let first = server
+            .mock("POST", "/app/installations/5/access_tokens")
+            .with_status(201)
+            .with_body(token_body("ghs_five", Duration::hours(1)))
+            .create_async()
+            .await;
+        let second = server
+            .mock("POST", "/app/installations/102/access_tokens")
+            .with_status(201)
+            .with_body(token_body("ghs_one_oh_two", Duration::hours(1)))
+            .create_async()
+            .await;
+
+        let auth = auth(&server);
+        assert_eq!(auth.installation_token("ORG-102").await.unwrap().as_deref(), Some("ghs_one_oh_two"));
+        assert_eq!(auth.installation_token("org-5").await.unwrap().as_deref(), Some("ghs_five"));
+        assert_eq!(auth.installation_token("someone-else").await.unwrap(), None);
+
+        first.assert_async().await;
+        second.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn caches_missing_installations_until_ttl_expires() {
+        let mut server = Server::new_async().await;
+        let installations = server
+            .mock("GET", "/app/installations")
+            .match_query(Matcher::Any)
+            .with_body(r#"[{"id": 7, "account": {"login": "octo-org"}}]"#)
+            .expect(2)
+            .create_async()
+            .await;
+
+        let auth = auth(&server);
+        assert_eq!(auth.installation_token("no-app-here").await.unwrap(), None);
+        assert_eq!(auth.installation_token("No-App-Here").await.unwrap(), None);
+
+        auth.missing_installations.write().await.insert(
+            "no-app-here".to_string(),
+            Utc::now() - Duration::seconds(MISSING_INSTALLATION_TTL_SECS + 1),
+        );
+        assert_eq!(auth.installation_token("no-app-here").await.unwrap(), None);
+
+        installations.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn refreshing_one_installation_does_not_block_another() {
+        let mut server = Server::new_async().await;
+        server
+            .mock("GET", "/app/installations")
+            .match_query(Matcher::Any)
+            .with_body(r#"[{"id": 7, "account": {"login": "slow-org"}}, {"id": 8, "account": {"login": "fast-org"}}]"#)
+            .create_async()
+            .await;
+        server
+            .mock("POST", "/app/installations/7/access_tokens")
+            .with_status(201)
+            .with_chunked_body(|writer| {
+                std::thread::sleep(std::time::Duration::from_millis(500));
+                writer.write_all(token_body("ghs_slow", Duration::hours(1)).as_bytes())
+            })
+            .create_async()
+            .await;
+
+        let auth = std::sync::Arc::new(auth(&server));
+        auth.tokens.write().await.insert(8, InstallationToken {
+            token: "ghs_fast".to_string(),
+            expires_at: Utc::now() + Duration::hours(1),
+        });
+        // Resolve both installations up front so only the exchange is in flight.
+        auth.installation_id("fast-org").await.unwrap();
+
+        let slow = tokio::spawn({
+            let auth = auth.clone();
+            async move { auth.installation_token("slow-org").await }
+        });
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+
+        let fast = tokio::time::timeout(std::time::Duration::from_millis(200), auth.installation_token("fast-org"))
+            .await
+            .expect("cached token was blocked by another installation's refresh");
+        assert_eq!(fast.unwrap().as_deref(), Some("ghs_fast"));
+        assert_eq!(slow.await.unwrap().unwrap().as_deref(), Some("ghs_slow"));
+    }
+}
@@ -0,0 +1,375 @@
+use crate::models::source::{FileChange, ProviderKind, RepoRef, SourceCommit, SourceFile, SourceRepository};
+use crate::services::provider::SourceProvider;
+use crate::services::rate_limit::RequestBudget;
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use reqwest::header::{HeaderMap, HeaderValue, USER_AGENT};
+use regex::Regex;
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use log::{error, debug};
+
+pub const GITLAB_URL: &str = "https://gitlab.com";
+const MAX_PER_PAGE: usize = 100;
+
+#[derive(Debug, Deserialize)]
+struct Project {
+    path_with_namespace: String,
+    web_url: String,
+    default_branch: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CommitSummary {
+    id: String,
+    committed_date: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CommitDiff {
+    new_path: String,
+    diff: String,
+    #[serde(default)]
+    new_file: bool,
+    #[serde(default)]
+    renamed_file: bool,
+    #[serde(default)]
+    deleted_file: bool,
+}
+
+pub struct GitLabClient {
+    client: reqwest::Client,
+    base_url: String,
+    url_regex: Regex,
+    budget: RequestBudget,
+}
+
+impl GitLabClient {
+    pub fn new(base_url: &str, token: Option<String>, max_concurrent_requests: usize) -> Result<Self> {
+        let base_url = base_url.trim_end_matches('/').to_string();
+
+        let mut headers = HeaderMap::new();
+        headers.insert(USER_AGENT, HeaderValue::from_static("github-secret-scanner"));
+
+        if let Some(t) = token {
+            headers.insert("PRIVATE-TOKEN", HeaderValue::from_str(&t)?);
+        }
+
+        let client = reqwest::Client::builder()
+            .default_headers(headers)
+            .build()?;
+
+        let host = base_url
+            .trim_start_matches("https://")
+            .trim_start_matches("http://");
+        // Anchored on the scheme and host, so `https://mygitlab.com/...` or `https://evil/gitlab.com/...` don't match.
+        let url_regex = Regex::new(&format!(
+            r"^(?:https?://|ssh://git@){}/([^\s]+?/[^\s/]+?)(?:\.git)?(?:/-/[^\s]*)?/?$",
+            regex::escape(host)
+        ))?;
+
+        Ok(Self {
+            client,
+            base_url,
+            url_regex,
+            budget: RequestBudget::new(max_concurrent_requests, "ratelimit-"),
+        })
+    }
+
+    fn project_url(&self, repo: &RepoRef) -> String {
+        format!("{}/api/v4/projects/{}", self.base_url, encode_path(&repo.full_name()))
+    }
+
+    async fn get(&self, url: &str) -> Result<reqwest::Response> {
+        debug!("GET {}", url);
+        let _permit = self.budget.acquire().await?;
+        let response = self.client.get(url).send().await?;
+        self.budget.record(response.headers());
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            error!("GitLab API error {}: {}", status, error_text);
+            return Err(anyhow!("GitLab API error: {}", status));
+        }
+
+        Ok(response)
+    }
+
+    // GitLab caps `per_page` at 100, so later pages are followed through `x-next-page` (or a same-host `Link: rel="next"`
+    // when the count headers are omitted) until `limit` items are read.
+    async fn get_pages<T: DeserializeOwned>(&self, url: &str, limit: usize) -> Result<Vec<T>> {
+        let separator = if url.contains('?') { '&' } else { '?' };
+        let listing_url = format!("{}{}per_page={}", url, separator, limit.clamp(1, MAX_PER_PAGE));
+        let mut next_url = Some(format!("{}&page=1", listing_url));
+        let mut items = Vec::new();
+
+        while let Some(url) = next_url.take() {
+            let response = self.get(&url).await?;
+            let next_page = response
+                .headers()
+                .get("x-next-page")
+                .and_then(|v| v.to_str().ok())
+                .map(str::trim)
+                .filter(|page| !page.is_empty())
+                .map(|page| format!("{}&page={}", listing_url, page))
+                .or_else(|| {
+                    response
+                        .headers()
+                        .get(reqwest::header::LINK)
+                        .and_then(|v| v.to_str().ok())
+                        .and_then(next_link)
+                        .filter(|link| link.starts_with(&format!("{}/", self.base_url)))
+                });
+
+            let batch: Vec<T> = response.json().await?;
+            if batch.is_empty() {
+                break;
+            }
+            items.extend(batch);
+            if items.len() < limit {
+                next_url = next_page;
+            }
+        }
+
+        items.truncate(limit);
+        Ok(items)
+    }
+}
+
+fn next_link(header: &str) -> Option<String> {
+    header
+        .split(',')
+        .find(|link| link.contains(r#"rel="next""#))
+        .and_then(|link| Some(link.split_once('<')?.1.split_once('>')?.0.to_string()))
+}
+
+#[async_trait]
+impl SourceProvider for GitLabClient {
+    fn kind(&self) -> ProviderKind {
+        ProviderKind::GitLab
+    }
+
+    fn parse_repo_url(&self, url: &str) -> Option<RepoRef> {
+        let caps = self.url_regex.captures(url.trim())?;
+        let path = caps.get(1)?.as_str();
+        let (owner, repo) = path.rsplit_once('/')?;
+
+        Some(RepoRef {
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+        })
+    }
+
+    async fn get_repository(&self, repo: &RepoRef) -> Result<SourceRepository> {
+        let response = self.get(&self.project_url(repo)).await?;
+        let project: Project = response.json().await?;
+
+        Ok(SourceRepository {
+            full_name: project.path_with_namespace,
+            web_url: project.web_url,
+            default_branch: project.default_branch,
+        })
+    }
+
+    async fn list_commits(&self, repo: &RepoRef, since: Option<&str>, per_page: u32) -> Result<Vec<SourceCommit>> {
+        let mut url = format!("{}/repository/commits", self.project_url(repo));
+
+        if let Some(since_date) = since {
+            url.push_str(&format!("?since={}", encode_path(since_date)));
+        }
+
+        let commits: Vec<CommitSummary> = self.get_pages(&url, per_page as usize).await?;
+
+        Ok(commits
+            .into_iter()
+            .map(|commit| SourceCommit {
+                sha: commit.id,
+                date: commit.committed_date,
+                files: Vec::new(),
+            })
+            .collect())
+    }
+
+    async fn get_commit(&self, repo: &RepoRef, sha: &str) -> Result<SourceCommit> {
+        let commit_url = format!("{}/repository/commits/{}", self.project_url(repo), sha);
+        let commit: CommitSummary = self.get(&commit_url).await?.json().await?;
+
+        let diff_url = format!("{}/diff", commit_url);
+        let diffs: Vec<CommitDiff> = self.get_pages(&diff_url, usize::MAX).await?;
+
+        let files = diffs
+            .into_iter()
+            .map(|diff| SourceFile {
+                change: if diff.new_file {
+                    FileChange::Added
+                } else if diff.deleted_file {
+                    FileChange::Removed
+                } else if diff.renamed_file {
+                    FileChange::Renamed
+                } else {
+                    FileChange::Modified
+                },
+                path: diff.new_path,
+                patch: Some(diff.diff),
+            })
+            .collect();
+
+        Ok(SourceCommit {
+            sha: commit.id,
+            date: commit.committed_date,
+            files,
+        })
+    }
+
+    async fn get_file_content(&self, repo: &RepoRef, path: &str, ref_sha: &str) -> Result<Vec<u8>> {
+        let url = format!(
+            "{}/repository/files/{}/raw?ref={}",
+            self.project_url(repo), encode_path(path), ref_sha
+        );
+
+        let bytes = self.get(&url).await?.bytes().await?;
+        Ok(bytes.to_vec())
+    }
+}
+
+fn encode_path(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => encoded.push(byte as char),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::{Matcher, Server};
+
+    fn parse(base_url: &str, url: &str) -> Option<(String, String)> {
+        let client = GitLabClient::new(base_url, None, 1).unwrap();
+        client.parse_repo_url(url).map(|repo| (repo.owner, repo.repo))
+    }
+
+    fn repo(owner: &str, repo: &str) -> Option<(String, String)> {
+        Some((owner.to_string(), repo.to_string()))
+    }
+
+    #[test]
+    fn parses_project_urls() {
+        let cases = [
+            ("https://gitlab.com/group/project", repo("group", "project")),
+            ("https://gitlab.com/group/project/", repo("group", "project")),
+            ("https://gitlab.com/group/sub/deeper/project", repo("group/sub/deeper", "project")),
+            ("https://gitlab.com/group/project.git", repo("group", "project")),
+            ("ssh://git@gitlab.com/group/sub/project.git", repo("group/sub", "project")),
+            ("https://gitlab.com/group/sub/project/-/tree/main/src", repo("group/sub", "project")),
+            ("https://gitlab.com/group/project/-/merge_requests/12", repo("group", "project")),
+            ("https://gitlab.com/project", None),
+            ("https://mygitlab.com/group/project", None),
+            ("https://evil/gitlab.com/group/project", None),
+            ("https://gitlab.com.evil.io/group/project", None),
+            ("see https://gitlab.com/group/project", None),
+        ];
+
+        for (url, expected) in cases {
+            assert_eq!(parse(GITLAB_URL, url), expected, "{}", url);
+        }
+    }
+
+    #[test]
+    fn parses_self_hosted_project_urls() {
+        assert_eq!(parse("https://gitlab.corp/", "https://gitlab.corp/team/app"), repo("team", "app"));
+        assert_eq!(parse("https://gitlab.corp:8443", "https://gitlab.corp:8443/team/app.git"), repo("team", "app"));
+        assert_eq!(parse("https://gitlab.corp", "https://gitlab.com/team/app"), None);
+    }
+
+    #[tokio::test]
+    async fn maps_commit_and_follows_diff_pages() {
+        let mut server = Server::new_async().await;
+        let commit_path = "/api/v4/projects/group%2Fsub%2Fproject/repository/commits/abc123";
+        let _commit = server
+            .mock("GET", commit_path)
+            .with_body(r#"{"id": "abc123", "committed_date": "2024-03-04T05:06:07Z"}"#)
+            .create_async()
+            .await;
+        let _first = server
+            .mock("GET", format!("{}/diff", commit_path).as_str())
+            .match_query(Matcher::UrlEncoded("page".into(), "1".into()))
+            .with_header("x-next-page", "2")
+            .with_body(
+                serde_json::json!([
+                    { "new_path": "added.env", "diff": "+KEY=1", "new_file": true },
+                    { "new_path": "gone.env", "diff": "-KEY=1", "deleted_file": true },
+                ])
+                .to_string(),
+            )
+            .create_async()
+            .await;
+        let _second = server
+            .mock("GET", format!("{}/diff", commit_path).as_str())
+            .match_query(Matcher::UrlEncoded("page".into(), "2".into()))
+            .with_header("x-next-page", "")
+            .with_body(
+                serde_json::json!([
+                    { "new_path": "moved.env", "diff": "", "renamed_file": true },
+                    { "new_path": "changed.env", "diff": "+KEY=2" },
+                ])
+                .to_string(),
+            )
+            .create_async()
+            .await;
+
+        let client = GitLabClient::new(&server.url(), None, 2).unwrap();
+        let project = RepoRef { owner: "group/sub".to_string(), repo: "project".to_string() };
+        let commit = client.get_commit(&project, "abc123").await.unwrap();
+
+        assert_eq!(commit.sha, "abc123");
+        assert_eq!(commit.date.to_rfc3339(), "2024-03-04T05:06:07+00:00");
+        let files: Vec<(&str, FileChange)> = commit.files.iter().map(|f| (f.path.as_str(), f.change)).collect();
+        assert_eq!(
+            files,
+            vec![
+                ("added.env", FileChange::Added),
+                ("gone.env", FileChange::Removed),
+                ("moved.env", FileChange::Renamed),
+                ("changed.env", FileChange::Modified),
+            ]
+        );
+        assert_eq!(commit.files[0].patch.as_deref(), Some("+KEY=1"));
+    }
+
+    #[tokio::test]
+    async fn commit_listing_stops_at_the_limit() {
+        let mut server = Server::new_async().await;
+        let page: Vec<serde_json::Value> = (0..MAX_PER_PAGE)
+            .map(|i| serde_json::json!({ "id": format!("c{}", i), "committed_date": "2024-01-01T00:00:00Z" }))
+            .collect();
+        let listing = server
+            .mock("GET", "/api/v4/projects/group%2Fproject/repository/commits")
+            .match_query(Matcher::Any)
+            .with_header("x-next-page", "2")
+            .with_body(serde_json::Value::Array(page).to_string())
+            .expect(2)
+            .create_async()
+            .await;
+
+        let client = GitLabClient::new(&server.url(), None, 2).unwrap();
+        let project = RepoRef { owner: "group".to_string(), repo: "project".to_string() };
+        let commits = client.list_commits(&project, None, 150).await.unwrap();
+
+        listing.assert_async().await;
+        assert_eq!(commits.len(), 150);
+    }
+
+    #[test]
+    fn reads_next_link() {
+        let header = r#"<https://gitlab.com/api/v4/x?page=1>; rel="prev", <https://gitlab.com/api/v4/x?page=3>; rel="next""#;
+        assert_eq!(next_link(header).as_deref(), Some("https://gitlab.com/api/v4/x?page=3"));
+        assert_eq!(next_link(r#"<https://gitlab.com/api/v4/x?page=1>; rel="first""#), None);
+    }
+}
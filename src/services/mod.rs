@@ -1,4 +1,9 @@
 pub mod github;
+pub mod github_app;
+pub mod gitlab;
+pub mod provider;
+pub mod rate_limit;
 pub mod scanner;
 pub mod gemini;
-pub mod state;
\ No newline at end of file
+pub mod state;
+pub mod verifier;
\ No newline at end of file
@@ -0,0 +1,36 @@
+use crate::models::source::{ProviderKind, RepoRef, SourceCommit, SourceRepository};
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use std::sync::Arc;
+
+#[async_trait]
+pub trait SourceProvider: Send + Sync {
+    fn kind(&self) -> ProviderKind;
+
+    fn parse_repo_url(&self, url: &str) -> Option<RepoRef>;
+
+    async fn get_repository(&self, repo: &RepoRef) -> Result<SourceRepository>;
+
+    async fn list_commits(&self, repo: &RepoRef, since: Option<&str>, per_page: u32) -> Result<Vec<SourceCommit>>;
+
+    async fn get_commit(&self, repo: &RepoRef, sha: &str) -> Result<SourceCommit>;
+
+    async fn get_file_content(&self, repo: &RepoRef, path: &str, ref_sha: &str) -> Result<Vec<u8>>;
+}
+
+pub struct ProviderRegistry {
+    providers: Vec<Arc<dyn SourceProvider>>,
+}
+
+impl ProviderRegistry {
+    pub fn new(providers: Vec<Arc<dyn SourceProvider>>) -> Self {
+        Self { providers }
+    }
+
+    pub fn resolve(&self, url: &str) -> Result<(Arc<dyn SourceProvider>, RepoRef)> {
+        self.providers
+            .iter()
+            .find_map(|provider| provider.parse_repo_url(url).map(|repo| (provider.clone(), repo)))
+            .ok_or_else(|| anyhow!("Unsupported repository URL: {}", url))
+    }
+}
@@ -0,0 +1,79 @@
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Utc};
+use log::warn;
+use reqwest::header::HeaderMap;
+use std::sync::Mutex;
+use tokio::sync::{Semaphore, SemaphorePermit};
+
+const RATE_LIMIT_RESERVE: u64 = 10;
+const MAX_RATE_LIMIT_WAIT_SECS: i64 = 300;
+
+#[derive(Debug, Clone, Copy)]
+struct RateLimitStatus {
+    remaining: u64,
+    reset_at: DateTime<Utc>,
+}
+
+pub struct RequestBudget {
+    permits: Semaphore,
+    header_prefix: &'static str,
+    status: Mutex<Option<RateLimitStatus>>,
+}
+
+impl RequestBudget {
+    pub fn new(max_concurrent_requests: usize, header_prefix: &'static str) -> Self {
+        Self {
+            permits: Semaphore::new(max_concurrent_requests.max(1)),
+            header_prefix,
+            status: Mutex::new(None),
+        }
+    }
+
+    pub async fn acquire(&self) -> Result<SemaphorePermit<'_>> {
+        let permit = self.permits.acquire().await?;
+
+        let wait_until = {
+            let mut status = self.status.lock().unwrap();
+            match status.as_mut() {
+                Some(s) if s.remaining <= RATE_LIMIT_RESERVE && s.reset_at > Utc::now() => Some(s.reset_at),
+                Some(s) => {
+                    // Count in-flight requests against the budget until the next response updates it.
+                    s.remaining = s.remaining.saturating_sub(1);
+                    None
+                }
+                None => None,
+            }
+        };
+
+        if let Some(reset_at) = wait_until {
+            let wait = reset_at - Utc::now();
+            if wait.num_seconds() > MAX_RATE_LIMIT_WAIT_SECS {
+                return Err(anyhow!("API rate limit exhausted until {}", reset_at));
+            }
+
+            warn!("API rate limit nearly exhausted, waiting {}s for reset", wait.num_seconds());
+            tokio::time::sleep(wait.to_std().unwrap_or_default()).await;
+            *self.status.lock().unwrap() = None;
+        }
+
+        Ok(permit)
+    }
+
+    pub fn record(&self, headers: &HeaderMap) {
+        let header = |name: &str| {
+            headers
+                .get(format!("{}{}", self.header_prefix, name))
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.parse::<i64>().ok())
+        };
+
+        if let (Some(remaining), Some(reset)) = (header("remaining"), header("reset"))
+            && let Some(reset_at) = DateTime::from_timestamp(reset, 0)
+        {
+            *self.status.lock().unwrap() = Some(RateLimitStatus {
+                remaining: remaining.max(0) as u64,
+                reset_at,
+            });
+        }
+    }
+}
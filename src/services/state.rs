@@ -1,50 +1,842 @@
-use crate::models::scan::ScanState;
-use anyhow::Result;
+use crate::models::scan::{Finding, FindingStatus, ScanState, ScanStats, TrackedFinding, Triage};
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Utc};
+use log::info;
+use rusqlite::{Connection, OptionalExtension, Row, params};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
 use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
-use tokio::sync::RwLock;
+use std::sync::{Arc, Mutex};
+
+const MIGRATIONS: &[&str] = &[
+    r#"
+    CREATE TABLE scans (
+        repo_url TEXT PRIMARY KEY,
+        owner TEXT NOT NULL,
+        repo TEXT NOT NULL,
+        scan_mode TEXT NOT NULL,
+        last_scanned_commit_sha TEXT NOT NULL,
+        last_scan_timestamp TEXT NOT NULL,
+        total_commits_scanned INTEGER NOT NULL,
+        findings_count INTEGER NOT NULL,
+        status TEXT NOT NULL
+    );
+
+    CREATE TABLE findings (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        repo_url TEXT NOT NULL REFERENCES scans(repo_url),
+        secret_type TEXT NOT NULL,
+        severity TEXT NOT NULL,
+        file_path TEXT NOT NULL,
+        line_number INTEGER NOT NULL,
+        matched_text TEXT NOT NULL,
+        commit_sha TEXT NOT NULL,
+        commit_date TEXT NOT NULL,
+        description TEXT NOT NULL,
+        remediation TEXT NOT NULL,
+        source TEXT NOT NULL,
+        source_url TEXT
+    );
+    CREATE INDEX idx_findings_repo ON findings(repo_url);
+
+    CREATE TABLE scanned_commits (
+        repo_url TEXT NOT NULL REFERENCES scans(repo_url),
+        commit_sha TEXT NOT NULL,
+        scanned_at TEXT NOT NULL,
+        PRIMARY KEY (repo_url, commit_sha)
+    );
+
+    CREATE TABLE conversations (
+        conversation_id TEXT PRIMARY KEY,
+        repo_url TEXT NOT NULL,
+        updated_at TEXT NOT NULL
+    );
+    "#,
+    r#"
+    ALTER TABLE findings ADD COLUMN fingerprint TEXT NOT NULL DEFAULT '';
+    ALTER TABLE findings ADD COLUMN removed INTEGER NOT NULL DEFAULT 0;
+
+    CREATE TABLE tracked_findings (
+        repo_url TEXT NOT NULL REFERENCES scans(repo_url),
+        fingerprint TEXT NOT NULL,
+        secret_type TEXT NOT NULL,
+        severity TEXT NOT NULL,
+        file_path TEXT NOT NULL,
+        line_number INTEGER NOT NULL,
+        matched_text TEXT NOT NULL,
+        source_url TEXT,
+        first_seen TEXT NOT NULL,
+        last_seen TEXT NOT NULL,
+        first_commit_sha TEXT NOT NULL,
+        last_commit_sha TEXT NOT NULL,
+        status TEXT NOT NULL,
+        resolved_commit_sha TEXT,
+        PRIMARY KEY (repo_url, fingerprint)
+    );
+    CREATE INDEX idx_tracked_findings_status ON tracked_findings(repo_url, status);
+    "#,
+    r#"
+    ALTER TABLE tracked_findings ADD COLUMN triage_reason TEXT;
+    ALTER TABLE tracked_findings ADD COLUMN triage_actor TEXT;
+    ALTER TABLE tracked_findings ADD COLUMN triaged_at TEXT;
+    "#,
+    r#"
+    ALTER TABLE findings ADD COLUMN verification TEXT;
+    ALTER TABLE tracked_findings ADD COLUMN verification TEXT;
+    "#,
+    r#"
+    ALTER TABLE findings ADD COLUMN end_line_number INTEGER;
+    "#,
+    r#"
+    ALTER TABLE findings ADD COLUMN decoding TEXT;
+    "#,
+    r#"
+    ALTER TABLE scans ADD COLUMN stats TEXT;
+    "#,
+    r#"
+    ALTER TABLE findings ADD COLUMN config_key TEXT;
+    "#,
+    r#"
+    ALTER TABLE findings ADD COLUMN cell_index INTEGER;
+    "#,
+    r#"
+    ALTER TABLE findings ADD COLUMN start_column INTEGER;
+    ALTER TABLE findings ADD COLUMN end_column INTEGER;
+    "#,
+    r#"
+    CREATE TABLE settings (
+        name TEXT PRIMARY KEY,
+        value TEXT NOT NULL
+    );
+    "#,
+    r#"
+    DELETE FROM findings
+    WHERE fingerprint <> '' AND id NOT IN (
+        SELECT MAX(id) FROM findings WHERE fingerprint <> ''
+        GROUP BY repo_url, fingerprint, commit_sha, file_path, line_number, removed
+    );
+    CREATE UNIQUE INDEX idx_findings_occurrence
+        ON findings(repo_url, fingerprint, commit_sha, file_path, line_number, removed)
+        WHERE fingerprint <> '';
+    "#,
+];
+
+// Shorter prefixes are only accepted as exact fingerprints, so a guess like "a" can't pick off a lone finding.
+const MIN_FINGERPRINT_PREFIX: i64 = 8;
+const FINGERPRINT_KEY_SETTING: &str = "fingerprint_key";
+const GENERATED_KEY_BYTES: usize = 32;
 
 pub struct StateManager {
-    file_path: String,
-    states: RwLock<HashMap<String, ScanState>>,
+    conn: Arc<Mutex<Connection>>,
 }
 
 impl StateManager {
-    pub fn new(file_path: &str) -> Result<Self> {
-        let states = if Path::new(file_path).exists() {
-            let content = fs::read_to_string(file_path)?;
-            serde_json::from_str(&content)?
-        } else {
-            HashMap::new()
-        };
+    pub fn new(db_path: &str, legacy_json_path: Option<&str>) -> Result<Self> {
+        let mut conn = Connection::open(db_path)?;
+        conn.pragma_update(None, "journal_mode", "WAL")?;
+        conn.pragma_update(None, "foreign_keys", "ON")?;
+        Self::migrate(&mut conn)?;
+
+        if let Some(json_path) = legacy_json_path
+            && Path::new(json_path).exists()
+        {
+            Self::import_json_state(&mut conn, json_path)?;
+        }
 
         Ok(Self {
-            file_path: file_path.to_string(),
-            states: RwLock::new(states),
+            conn: Arc::new(Mutex::new(conn)),
         })
     }
 
-    pub async fn load_state(&self, repo_url: &str) -> Result<Option<ScanState>> {
-        let states = self.states.read().await;
-        Ok(states.get(repo_url).cloned())
+    fn migrate(conn: &mut Connection) -> Result<()> {
+        let version: usize = conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
+
+        for (idx, migration) in MIGRATIONS.iter().enumerate().skip(version) {
+            let tx = conn.transaction()?;
+            tx.execute_batch(migration)?;
+            tx.pragma_update(None, "user_version", idx + 1)?;
+            tx.commit()?;
+            info!("Applied state database migration {}", idx + 1);
+        }
+
+        Ok(())
     }
 
-    pub async fn save_state(&self, state: &ScanState) -> Result<()> {
-        let mut states = self.states.write().await;
-        states.insert(state.repo_url.clone(), state.clone());
-        self.persist(&states)?;
+    fn import_json_state(conn: &mut Connection, json_path: &str) -> Result<()> {
+        let content = fs::read_to_string(json_path)?;
+        let states: HashMap<String, ScanState> = serde_json::from_str(&content)?;
+
+        let tx = conn.transaction()?;
+        for state in states.values() {
+            Self::upsert_scan(&tx, state)?;
+        }
+        tx.commit()?;
+
+        let imported_path = format!("{}.imported", json_path);
+        fs::rename(json_path, &imported_path)?;
+        info!("Imported {} scan states from {} (moved to {})", states.len(), json_path, imported_path);
+
         Ok(())
     }
 
+    async fn with_conn<T, F>(&self, f: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut Connection) -> Result<T> + Send + 'static,
+    {
+        let conn = self.conn.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut conn = conn.lock().map_err(|_| anyhow!("State database lock poisoned"))?;
+            f(&mut conn)
+        })
+        .await?
+    }
+
+    pub async fn load_state(&self, repo_url: &str) -> Result<Option<ScanState>> {
+        let repo_url = repo_url.to_string();
+        self.with_conn(move |conn| {
+            let state = conn
+                .query_row("SELECT * FROM scans WHERE repo_url = ?1", params![repo_url], Self::scan_from_row)
+                .optional()?;
+            Ok(state)
+        })
+        .await
+    }
+
+    pub async fn record_scan(&self, state: &ScanState, commit_shas: &[String], findings: &[Finding]) -> Result<()> {
+        let state = state.clone();
+        let commit_shas = commit_shas.to_vec();
+        let findings = findings.to_vec();
+
+        self.with_conn(move |conn| {
+            let tx = conn.transaction()?;
+            Self::upsert_scan(&tx, &state)?;
+
+            let scanned_at = Utc::now().to_rfc3339();
+            for sha in &commit_shas {
+                tx.execute(
+                    "INSERT OR IGNORE INTO scanned_commits (repo_url, commit_sha, scanned_at) VALUES (?1, ?2, ?3)",
+                    params![state.repo_url, sha, scanned_at],
+                )?;
+            }
+
+            for finding in &findings {
+                tx.execute(
+                    "INSERT INTO findings (repo_url, secret_type, severity, file_path, line_number, matched_text,
+                        commit_sha, commit_date, description, remediation, source, source_url, fingerprint, removed,
+                        verification, end_line_number, decoding, config_key, cell_index, start_column, end_column)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21)
+                     ON CONFLICT(repo_url, fingerprint, commit_sha, file_path, line_number, removed) WHERE fingerprint <> ''
+                     DO UPDATE SET
+                        secret_type = excluded.secret_type,
+                        severity = excluded.severity,
+                        matched_text = excluded.matched_text,
+                        description = excluded.description,
+                        remediation = excluded.remediation,
+                        source_url = excluded.source_url,
+                        verification = excluded.verification,
+                        end_line_number = excluded.end_line_number,
+                        decoding = excluded.decoding,
+                        config_key = excluded.config_key,
+                        cell_index = excluded.cell_index,
+                        start_column = excluded.start_column,
+                        end_column = excluded.end_column",
+                    params![
+                        state.repo_url,
+                        finding.secret_type,
+                        to_sql_enum(&finding.severity)?,
+                        finding.file_path,
+                        finding.line_number as i64,
+                        finding.matched_text,
+                        finding.commit_sha,
+                        finding.commit_date.to_rfc3339(),
+                        finding.description,
+                        finding.remediation,
+                        to_sql_enum(&finding.source)?,
+                        finding.source_url,
+                        finding.fingerprint,
+                        finding.removed,
+                        finding.verification.as_ref().map(serde_json::to_string).transpose()?,
+                        finding.end_line_number.map(|line| line as i64),
+                        (!finding.decoding.is_empty()).then(|| serde_json::to_string(&finding.decoding)).transpose()?,
+                        finding.config_key,
+                        finding.cell_index.map(|cell| cell as i64),
+                        finding.match_span.map(|span| span.start_column as i64),
+                        finding.match_span.map(|span| span.end_column as i64),
+                    ],
+                )?;
+            }
+
+            // Oldest commits first; within a commit removals go before additions so a moved secret stays open.
+            let mut ordered: Vec<&Finding> = findings.iter().collect();
+            ordered.sort_by_key(|f| (f.commit_date, !f.removed));
+            for finding in ordered {
+                Self::track_finding(&tx, &state.repo_url, finding)?;
+            }
+
+            // Counted from what is stored rather than added up per scan, so rescanning the same commits counts nothing twice.
+            tx.execute(
+                "UPDATE scans SET
+                    total_commits_scanned = (SELECT COUNT(*) FROM scanned_commits WHERE repo_url = ?1),
+                    findings_count = (SELECT COUNT(*) FROM tracked_findings WHERE repo_url = ?1)
+                 WHERE repo_url = ?1",
+                params![state.repo_url],
+            )?;
+
+            tx.commit()?;
+            Ok(())
+        })
+        .await
+    }
+
+    pub async fn list_findings(&self, repo_url: &str, status: Option<FindingStatus>) -> Result<Vec<TrackedFinding>> {
+        let repo_url = repo_url.to_string();
+        let status = status.map(|s| to_sql_enum(&s)).transpose()?;
+
+        self.with_conn(move |conn| {
+            let mut stmt = conn.prepare(
+                "SELECT * FROM tracked_findings
+                 WHERE repo_url = ?1 AND (?2 IS NULL OR status = ?2)
+                 ORDER BY last_seen DESC",
+            )?;
+            let findings = stmt
+                .query_map(params![repo_url, status], Self::tracked_from_row)?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+            Ok(findings)
+        })
+        .await
+    }
+
+    pub async fn set_finding_status(
+        &self,
+        repo_url: &str,
+        fingerprint: &str,
+        status: FindingStatus,
+        reason: &str,
+        actor: &str,
+    ) -> Result<TrackedFinding> {
+        let repo_url = repo_url.to_string();
+        let fingerprint = fingerprint.to_lowercase();
+        let reason = reason.to_string();
+        let actor = actor.to_string();
+
+        self.with_conn(move |conn| {
+            let tx = conn.transaction()?;
+
+            // Accept the short fingerprint prefixes shown in chat as long as they are unambiguous.
+            let matches = {
+                let mut stmt = tx.prepare(
+                    "SELECT * FROM tracked_findings
+                     WHERE repo_url = ?1
+                       AND (fingerprint = ?2 OR (length(?2) >= ?3 AND substr(fingerprint, 1, length(?2)) = ?2))",
+                )?;
+                stmt.query_map(params![repo_url, fingerprint, MIN_FINGERPRINT_PREFIX], Self::tracked_from_row)?
+                    .collect::<rusqlite::Result<Vec<_>>>()?
+            };
+
+            let mut tracked = match matches.len() {
+                0 => return Err(anyhow!("No finding {} recorded for {}", fingerprint, repo_url)),
+                1 => matches.into_iter().next().unwrap(),
+                n => return Err(anyhow!("Fingerprint {} is ambiguous ({} findings match)", fingerprint, n)),
+            };
+
+            let triaged_at = Utc::now();
+            tx.execute(
+                "UPDATE tracked_findings
+                 SET status = ?3, triage_reason = ?4, triage_actor = ?5, triaged_at = ?6
+                 WHERE repo_url = ?1 AND fingerprint = ?2",
+                params![
+                    repo_url,
+                    tracked.fingerprint,
+                    to_sql_enum(&status)?,
+                    reason,
+                    actor,
+                    triaged_at.to_rfc3339(),
+                ],
+            )?;
+            tx.commit()?;
+
+            tracked.status = status;
+            tracked.triage = Some(Triage {
+                status,
+                reason,
+                actor,
+                triaged_at,
+            });
+            Ok(tracked)
+        })
+        .await
+    }
+
+    pub async fn triaged_findings(&self, repo_url: &str) -> Result<HashMap<String, Triage>> {
+        let repo_url = repo_url.to_string();
+        self.with_conn(move |conn| {
+            let mut stmt = conn.prepare(
+                "SELECT * FROM tracked_findings WHERE repo_url = ?1 AND triaged_at IS NOT NULL",
+            )?;
+            let triaged = stmt
+                .query_map(params![repo_url], Self::tracked_from_row)?
+                .filter_map(|row| match row {
+                    Ok(tracked) => tracked.triage.map(|triage| Ok((tracked.fingerprint, triage))),
+                    Err(e) => Some(Err(e)),
+                })
+                .collect::<rusqlite::Result<HashMap<_, _>>>()?;
+            Ok(triaged)
+        })
+        .await
+    }
+
+    pub async fn scanned_commits(&self, repo_url: &str) -> Result<Vec<String>> {
+        let repo_url = repo_url.to_string();
+        self.with_conn(move |conn| {
+            let mut stmt = conn.prepare("SELECT commit_sha FROM scanned_commits WHERE repo_url = ?1")?;
+            let shas = stmt
+                .query_map(params![repo_url], |row| row.get(0))?
+                .collect::<rusqlite::Result<Vec<String>>>()?;
+            Ok(shas)
+        })
+        .await
+    }
+
     pub async fn list_all_states(&self) -> Result<Vec<ScanState>> {
-        let states = self.states.read().await;
-        Ok(states.values().cloned().collect())
+        self.with_conn(|conn| {
+            let mut stmt = conn.prepare("SELECT * FROM scans ORDER BY repo_url")?;
+            let states = stmt
+                .query_map([], Self::scan_from_row)?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+            Ok(states)
+        })
+        .await
+    }
+
+    pub async fn save_conversation(&self, conversation_id: &str, repo_url: &str) -> Result<()> {
+        let conversation_id = conversation_id.to_string();
+        let repo_url = repo_url.to_string();
+        self.with_conn(move |conn| {
+            conn.execute(
+                "INSERT INTO conversations (conversation_id, repo_url, updated_at) VALUES (?1, ?2, ?3)
+                 ON CONFLICT(conversation_id) DO UPDATE SET repo_url = excluded.repo_url, updated_at = excluded.updated_at",
+                params![conversation_id, repo_url, Utc::now().to_rfc3339()],
+            )?;
+            Ok(())
+        })
+        .await
+    }
+
+    pub async fn load_conversation(&self, conversation_id: &str) -> Result<Option<String>> {
+        let conversation_id = conversation_id.to_string();
+        self.with_conn(move |conn| {
+            let repo_url = conn
+                .query_row(
+                    "SELECT repo_url FROM conversations WHERE conversation_id = ?1",
+                    params![conversation_id],
+                    |row| row.get(0),
+                )
+                .optional()?;
+            Ok(repo_url)
+        })
+        .await
+    }
+
+    // The key generated on first start, used for fingerprints when FINGERPRINT_KEY is not set.
+    pub async fn generated_fingerprint_key(&self) -> Result<String> {
+        self.with_conn(|conn| {
+            let existing: Option<String> = conn
+                .query_row(
+                    "SELECT value FROM settings WHERE name = ?1",
+                    params![FINGERPRINT_KEY_SETTING],
+                    |row| row.get(0),
+                )
+                .optional()?;
+            if let Some(key) = existing {
+                return Ok(key);
+            }
+
+            let mut bytes = [0u8; GENERATED_KEY_BYTES];
+            getrandom::getrandom(&mut bytes).map_err(|e| anyhow!("Failed to generate fingerprint key: {}", e))?;
+            let key = hex::encode(bytes);
+            conn.execute(
+                "INSERT INTO settings (name, value) VALUES (?1, ?2)",
+                params![FINGERPRINT_KEY_SETTING, key],
+            )?;
+            info!("Generated a new fingerprint key");
+            Ok(key)
+        })
+        .await
     }
 
-    fn persist(&self, states: &HashMap<String, ScanState>) -> Result<()> {
-        let json = serde_json::to_string_pretty(states)?;
-        fs::write(&self.file_path, json)?;
+    fn upsert_scan(conn: &Connection, state: &ScanState) -> Result<()> {
+        conn.execute(
+            "INSERT INTO scans (repo_url, owner, repo, scan_mode, last_scanned_commit_sha, last_scan_timestamp,
+                total_commits_scanned, findings_count, status, stats)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+             ON CONFLICT(repo_url) DO UPDATE SET
+                owner = excluded.owner,
+                repo = excluded.repo,
+                scan_mode = excluded.scan_mode,
+                last_scanned_commit_sha = excluded.last_scanned_commit_sha,
+                last_scan_timestamp = excluded.last_scan_timestamp,
+                total_commits_scanned = excluded.total_commits_scanned,
+                findings_count = excluded.findings_count,
+                status = excluded.status,
+                stats = excluded.stats",
+            params![
+                state.repo_url,
+                state.owner,
+                state.repo,
+                to_sql_enum(&state.scan_mode)?,
+                state.last_scanned_commit_sha,
+                state.last_scan_timestamp.to_rfc3339(),
+                state.total_commits_scanned as i64,
+                state.findings_count as i64,
+                to_sql_enum(&state.status)?,
+                serde_json::to_string(&state.stats)?,
+            ],
+        )?;
         Ok(())
     }
-}
\ No newline at end of file
+
+    fn track_finding(conn: &Connection, repo_url: &str, finding: &Finding) -> Result<()> {
+        Self::adopt_legacy_fingerprints(conn, repo_url, finding)?;
+
+        let existing = conn
+            .query_row(
+                "SELECT * FROM tracked_findings WHERE repo_url = ?1 AND fingerprint = ?2",
+                params![repo_url, finding.fingerprint],
+                Self::tracked_from_row,
+            )
+            .optional()?;
+
+        let tracked = match existing {
+            None if finding.removed => return Ok(()),
+            None => TrackedFinding {
+                repo_url: repo_url.to_string(),
+                fingerprint: finding.fingerprint.clone(),
+                secret_type: finding.secret_type.clone(),
+                severity: finding.severity.clone(),
+                file_path: finding.file_path.clone(),
+                line_number: finding.line_number,
+                matched_text: finding.matched_text.clone(),
+                source_url: finding.source_url.clone(),
+                first_seen: finding.commit_date,
+                last_seen: finding.commit_date,
+                first_commit_sha: finding.commit_sha.clone(),
+                last_commit_sha: finding.commit_sha.clone(),
+                status: FindingStatus::Open,
+                resolved_commit_sha: None,
+                triage: None,
+                verification: finding.verification.clone(),
+            },
+            Some(mut tracked) if finding.removed => {
+                if tracked.triage.is_none()
+                    && tracked.status == FindingStatus::Open
+                    && finding.commit_date >= tracked.last_seen
+                {
+                    tracked.status = FindingStatus::Resolved;
+                    tracked.resolved_commit_sha = Some(finding.commit_sha.clone());
+                }
+                tracked
+            }
+            Some(mut tracked) => {
+                if finding.commit_date < tracked.first_seen {
+                    tracked.first_seen = finding.commit_date;
+                    tracked.first_commit_sha = finding.commit_sha.clone();
+                }
+                if finding.commit_date >= tracked.last_seen {
+                    tracked.last_seen = finding.commit_date;
+                    tracked.last_commit_sha = finding.commit_sha.clone();
+                    tracked.file_path = finding.file_path.clone();
+                    tracked.line_number = finding.line_number;
+                    tracked.source_url = finding.source_url.clone();
+                    if finding.verification.is_some() {
+                        tracked.verification = finding.verification.clone();
+                    }
+
+                    if tracked.triage.is_none() && tracked.status == FindingStatus::Resolved {
+                        tracked.status = FindingStatus::Open;
+                        tracked.resolved_commit_sha = None;
+                    }
+                }
+                tracked
+            }
+        };
+
+        conn.execute(
+            "INSERT INTO tracked_findings (repo_url, fingerprint, secret_type, severity, file_path, line_number,
+                matched_text, source_url, first_seen, last_seen, first_commit_sha, last_commit_sha, status,
+                resolved_commit_sha, verification)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)
+             ON CONFLICT(repo_url, fingerprint) DO UPDATE SET
+                file_path = excluded.file_path,
+                line_number = excluded.line_number,
+                source_url = excluded.source_url,
+                first_seen = excluded.first_seen,
+                last_seen = excluded.last_seen,
+                first_commit_sha = excluded.first_commit_sha,
+                last_commit_sha = excluded.last_commit_sha,
+                status = excluded.status,
+                resolved_commit_sha = excluded.resolved_commit_sha,
+                verification = excluded.verification",
+            params![
+                tracked.repo_url,
+                tracked.fingerprint,
+                tracked.secret_type,
+                to_sql_enum(&tracked.severity)?,
+                tracked.file_path,
+                tracked.line_number as i64,
+                tracked.matched_text,
+                tracked.source_url,
+                tracked.first_seen.to_rfc3339(),
+                tracked.last_seen.to_rfc3339(),
+                tracked.first_commit_sha,
+                tracked.last_commit_sha,
+                to_sql_enum(&tracked.status)?,
+                tracked.resolved_commit_sha,
+                tracked.verification.as_ref().map(serde_json::to_string).transpose()?,
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    // A finding tracked under an older fingerprint is re-keyed to the current one the next time it is seen, keeping
+    // its history and triage. If both rows already exist, the legacy row's triage moves over and the row is dropped.
+    fn adopt_legacy_fingerprints(conn: &Connection, repo_url: &str, finding: &Finding) -> Result<()> {
+        for legacy in finding.legacy_fingerprints.iter().filter(|legacy| **legacy != finding.fingerprint) {
+            let renamed = conn.execute(
+                "UPDATE tracked_findings SET fingerprint = ?3
+                 WHERE repo_url = ?1 AND fingerprint = ?2
+                   AND NOT EXISTS (SELECT 1 FROM tracked_findings WHERE repo_url = ?1 AND fingerprint = ?3)",
+                params![repo_url, legacy, finding.fingerprint],
+            )?;
+            if renamed == 0 {
+                conn.execute(
+                    "UPDATE tracked_findings AS current
+                     SET status = legacy.status, triage_reason = legacy.triage_reason,
+                         triage_actor = legacy.triage_actor, triaged_at = legacy.triaged_at
+                     FROM tracked_findings AS legacy
+                     WHERE current.repo_url = ?1 AND current.fingerprint = ?3 AND current.triaged_at IS NULL
+                       AND legacy.repo_url = ?1 AND legacy.fingerprint = ?2 AND legacy.triaged_at IS NOT NULL",
+                    params![repo_url, legacy, finding.fingerprint],
+                )?;
+                conn.execute(
+                    "DELETE FROM tracked_findings WHERE repo_url = ?1 AND fingerprint = ?2",
+                    params![repo_url, legacy],
+                )?;
+            }
+            // Occurrences already stored under the new fingerprint by this scan replace their legacy copies.
+            conn.execute(
+                "DELETE FROM findings AS legacy
+                 WHERE legacy.repo_url = ?1 AND legacy.fingerprint = ?2 AND EXISTS (
+                    SELECT 1 FROM findings AS current
+                    WHERE current.repo_url = ?1 AND current.fingerprint = ?3
+                      AND current.commit_sha = legacy.commit_sha AND current.file_path = legacy.file_path
+                      AND current.line_number = legacy.line_number AND current.removed = legacy.removed
+                 )",
+                params![repo_url, legacy, finding.fingerprint],
+            )?;
+            conn.execute(
+                "UPDATE findings SET fingerprint = ?3 WHERE repo_url = ?1 AND fingerprint = ?2",
+                params![repo_url, legacy, finding.fingerprint],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn tracked_from_row(row: &Row) -> rusqlite::Result<TrackedFinding> {
+        Ok(TrackedFinding {
+            repo_url: row.get("repo_url")?,
+            fingerprint: row.get("fingerprint")?,
+            secret_type: row.get("secret_type")?,
+            severity: from_sql_enum(row.get("severity")?)?,
+            file_path: row.get("file_path")?,
+            line_number: row.get::<_, i64>("line_number")? as usize,
+            matched_text: row.get("matched_text")?,
+            source_url: row.get("source_url")?,
+            first_seen: from_sql_timestamp(row.get("first_seen")?)?,
+            last_seen: from_sql_timestamp(row.get("last_seen")?)?,
+            first_commit_sha: row.get("first_commit_sha")?,
+            last_commit_sha: row.get("last_commit_sha")?,
+            status: from_sql_enum(row.get("status")?)?,
+            resolved_commit_sha: row.get("resolved_commit_sha")?,
+            triage: match row.get::<_, Option<String>>("triaged_at")? {
+                Some(triaged_at) => Some(Triage {
+                    status: from_sql_enum(row.get("status")?)?,
+                    reason: row.get::<_, Option<String>>("triage_reason")?.unwrap_or_default(),
+                    actor: row.get::<_, Option<String>>("triage_actor")?.unwrap_or_default(),
+                    triaged_at: from_sql_timestamp(triaged_at)?,
+                }),
+                None => None,
+            },
+            verification: match row.get::<_, Option<String>>("verification")? {
+                Some(json) => Some(serde_json::from_str(&json).map_err(|e| {
+                    rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(e))
+                })?),
+                None => None,
+            },
+        })
+    }
+
+    fn scan_from_row(row: &Row) -> rusqlite::Result<ScanState> {
+        Ok(ScanState {
+            repo_url: row.get("repo_url")?,
+            owner: row.get("owner")?,
+            repo: row.get("repo")?,
+            scan_mode: from_sql_enum(row.get("scan_mode")?)?,
+            last_scanned_commit_sha: row.get("last_scanned_commit_sha")?,
+            last_scan_timestamp: from_sql_timestamp(row.get("last_scan_timestamp")?)?,
+            total_commits_scanned: row.get::<_, i64>("total_commits_scanned")? as usize,
+            findings_count: row.get::<_, i64>("findings_count")? as usize,
+            status: from_sql_enum(row.get("status")?)?,
+            stats: match row.get::<_, Option<String>>("stats")? {
+                Some(json) => serde_json::from_str(&json).map_err(|e| {
+                    rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(e))
+                })?,
+                None => ScanStats::default(),
+            },
+        })
+    }
+}
+
+fn to_sql_enum<T: Serialize>(value: &T) -> Result<String> {
+    match serde_json::to_value(value)? {
+        serde_json::Value::String(s) => Ok(s),
+        other => Err(anyhow!("Expected a unit enum variant, got {}", other)),
+    }
+}
+
+fn from_sql_enum<T: DeserializeOwned>(value: String) -> rusqlite::Result<T> {
+    serde_json::from_value(serde_json::Value::String(value))
+        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(e)))
+}
+
+fn from_sql_timestamp(value: String) -> rusqlite::Result<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(&value)
+        .map(|d| d.with_timezone(&Utc))
+        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::scan::{ScanMode, ScanStatus};
+
+    const REPO_URL: &str = "https://github.com/octocat/hello";
+
+    fn scan_state() -> ScanState {
+        ScanState {
+            repo_url: REPO_URL.to_string(),
+            owner: "octocat".to_string(),
+            repo: "hello".to_string(),
+            scan_mode: ScanMode::Quick,
+            last_scanned_commit_sha: "abc123".to_string(),
+            last_scan_timestamp: Utc::now(),
+            total_commits_scanned: 1,
+            findings_count: 1,
+            status: ScanStatus::Completed,
+            stats: ScanStats::default(),
+        }
+    }
+
+    fn finding(fingerprint: &str, legacy_fingerprints: &[&str]) -> Finding {
+        let mut finding: Finding = serde_json::from_value(serde_json::json!({
+            "secret_type": "AWS Secret Access Key",
+            "severity": "Critical",
+            "file_path": ".env",
+            "line_number": 1,
+            "matched_text": "********",
+            "commit_sha": "abc123",
+            "commit_date": "2024-01-01T00:00:00Z",
+            "description": "",
+            "remediation": "",
+            "fingerprint": fingerprint,
+        }))
+        .unwrap();
+        finding.legacy_fingerprints = legacy_fingerprints.iter().map(|f| f.to_string()).collect();
+        finding
+    }
+
+    #[tokio::test]
+    async fn generated_fingerprint_key_is_persisted() {
+        let path = std::env::temp_dir().join(format!("secret-detector-{}.db", uuid::Uuid::new_v4()));
+        let path = path.to_str().unwrap();
+
+        let key = StateManager::new(path, None).unwrap().generated_fingerprint_key().await.unwrap();
+        let reloaded = StateManager::new(path, None).unwrap().generated_fingerprint_key().await.unwrap();
+        for suffix in ["", "-wal", "-shm"] {
+            let _ = fs::remove_file(format!("{}{}", path, suffix));
+        }
+
+        assert_eq!(key.len(), 64);
+        assert_eq!(key, reloaded);
+    }
+
+    #[tokio::test]
+    async fn triage_follows_finding_to_its_new_fingerprint() {
+        let manager = StateManager::new(":memory:", None).unwrap();
+        manager.record_scan(&scan_state(), &[], &[finding("old", &[])]).await.unwrap();
+        manager.set_finding_status(REPO_URL, "old", FindingStatus::Ignored, "test fixture", "alice").await.unwrap();
+
+        manager.record_scan(&scan_state(), &[], &[finding("new", &["old"])]).await.unwrap();
+
+        let triaged = manager.triaged_findings(REPO_URL).await.unwrap();
+        assert_eq!(triaged.keys().collect::<Vec<_>>(), vec!["new"]);
+        assert_eq!(triaged["new"].status, FindingStatus::Ignored);
+        assert_eq!(manager.list_findings(REPO_URL, None).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn triage_moves_onto_an_existing_untriaged_row() {
+        let manager = StateManager::new(":memory:", None).unwrap();
+        manager.record_scan(&scan_state(), &[], &[finding("old", &[]), finding("new", &[])]).await.unwrap();
+        manager.set_finding_status(REPO_URL, "old", FindingStatus::Acknowledged, "known", "alice").await.unwrap();
+
+        manager.record_scan(&scan_state(), &[], &[finding("new", &["old"])]).await.unwrap();
+
+        let tracked = manager.list_findings(REPO_URL, None).await.unwrap();
+        assert_eq!(tracked.len(), 1);
+        assert_eq!(tracked[0].fingerprint, "new");
+        assert_eq!(tracked[0].status, FindingStatus::Acknowledged);
+    }
+
+    #[tokio::test]
+    async fn short_fingerprint_prefixes_are_rejected() {
+        let manager = StateManager::new(":memory:", None).unwrap();
+        let fingerprint = "3fa9c1d2e4b7a6c5";
+        manager.record_scan(&scan_state(), &[], &[finding(fingerprint, &[])]).await.unwrap();
+
+        for prefix in ["", "3", "3fa9c1d"] {
+            assert!(
+                manager.set_finding_status(REPO_URL, prefix, FindingStatus::Ignored, "guess", "mallory").await.is_err(),
+                "prefix {:?} was accepted",
+                prefix
+            );
+        }
+
+        let tracked = manager.set_finding_status(REPO_URL, "3FA9C1D2", FindingStatus::Ignored, "fixture", "alice").await.unwrap();
+        assert_eq!(tracked.fingerprint, fingerprint);
+    }
+
+    #[tokio::test]
+    async fn rescanning_the_same_commits_counts_nothing_twice() {
+        let manager = StateManager::new(":memory:", None).unwrap();
+        let commits = vec!["abc123".to_string()];
+        let findings = [finding("3fa9c1d2e4b7a6c5", &[]), finding("9d8c7b6a5f4e3d2c", &[])];
+
+        for _ in 0..3 {
+            manager.record_scan(&scan_state(), &commits, &findings).await.unwrap();
+        }
+
+        let state = manager.load_state(REPO_URL).await.unwrap().unwrap();
+        assert_eq!(state.findings_count, 2);
+        assert_eq!(state.total_commits_scanned, 1);
+        let rows: i64 = manager
+            .with_conn(|conn| Ok(conn.query_row("SELECT COUNT(*) FROM findings", [], |row| row.get(0))?))
+            .await
+            .unwrap();
+        assert_eq!(rows, 2);
+    }
+}
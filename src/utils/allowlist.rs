@@ -0,0 +1,103 @@
+use crate::utils::patterns::{SecretPattern, is_likely_test_or_example};
+use anyhow::{Result, anyhow};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use regex::Regex;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+
+pub const INLINE_ALLOW_MARKER: &str = "secret-detector:allow";
+
+#[derive(Debug, Deserialize)]
+pub struct AllowlistConfig {
+    #[serde(default)]
+    pub paths: Vec<String>,
+    #[serde(default)]
+    pub stopwords: Vec<String>,
+    #[serde(default)]
+    pub rules: HashMap<String, Vec<String>>,
+    #[serde(default = "default_skip_test_paths")]
+    pub skip_test_paths: bool,
+}
+
+fn default_skip_test_paths() -> bool {
+    true
+}
+
+impl Default for AllowlistConfig {
+    fn default() -> Self {
+        Self {
+            paths: Vec::new(),
+            stopwords: Vec::new(),
+            rules: HashMap::new(),
+            skip_test_paths: default_skip_test_paths(),
+        }
+    }
+}
+
+pub struct Allowlist {
+    paths: GlobSet,
+    stopwords: Vec<String>,
+    rules: HashMap<String, Vec<Regex>>,
+    skip_test_paths: bool,
+}
+
+impl Allowlist {
+    pub fn load(config_path: Option<&str>) -> Result<Self> {
+        let config = match config_path {
+            Some(path) => {
+                let content = fs::read_to_string(path)?;
+                serde_json::from_str(&content)
+                    .map_err(|e| anyhow!("Invalid allowlist config {}: {}", path, e))?
+            }
+            None => AllowlistConfig::default(),
+        };
+
+        Self::from_config(config)
+    }
+
+    pub fn from_config(config: AllowlistConfig) -> Result<Self> {
+        let mut paths = GlobSetBuilder::new();
+        for pattern in &config.paths {
+            paths.add(Glob::new(pattern)?);
+        }
+
+        let rules = config.rules
+            .into_iter()
+            .map(|(rule, patterns)| {
+                let regexes = patterns
+                    .iter()
+                    .map(|p| Regex::new(p))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok((rule, regexes))
+            })
+            .collect::<Result<HashMap<_, _>>>()?;
+
+        Ok(Self {
+            paths: paths.build()?,
+            stopwords: config.stopwords.iter().map(|w| w.to_lowercase()).collect(),
+            rules,
+            skip_test_paths: config.skip_test_paths,
+        })
+    }
+
+    pub fn allows_path(&self, path: &str) -> bool {
+        (self.skip_test_paths && is_likely_test_or_example(path)) || self.paths.is_match(path)
+    }
+
+    pub fn allows_match(&self, rule: &SecretPattern, line: &str, matched_text: &str) -> bool {
+        if line.contains(INLINE_ALLOW_MARKER) {
+            return true;
+        }
+
+        let matched_lower = matched_text.to_lowercase();
+        if self.stopwords.iter().any(|w| matched_lower.contains(w.as_str())) {
+            return true;
+        }
+
+        [rule.id.as_str(), rule.name.as_str()]
+            .iter()
+            .filter_map(|key| self.rules.get(*key))
+            .any(|regexes| regexes.iter().any(|r| r.is_match(matched_text)))
+    }
+}
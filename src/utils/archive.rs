@@ -0,0 +1,271 @@
+use anyhow::Result;
+use flate2::read::GzDecoder;
+use log::{debug, warn};
+use std::io::{Cursor, Read};
+
+pub const NESTED_PATH_SEPARATOR: &str = "!/";
+const MAX_ARCHIVE_DEPTH: usize = 3;
+const MAX_ENTRY_BYTES: u64 = 10 * 1024 * 1024;
+const MAX_TOTAL_BYTES: u64 = 64 * 1024 * 1024;
+const MAX_ENTRIES: usize = 10_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ArchiveKind {
+    Zip,
+    Tar,
+    TarGz,
+    Gzip,
+}
+
+pub struct ArchiveEntry {
+    pub path: String,
+    pub content: Vec<u8>,
+}
+
+// Shared across nested archives so a zip bomb can't multiply its way past the limits.
+struct ExtractBudget {
+    remaining_bytes: u64,
+    remaining_entries: usize,
+}
+
+impl ExtractBudget {
+    fn exhausted(&self) -> bool {
+        self.remaining_bytes == 0 || self.remaining_entries == 0
+    }
+}
+
+pub fn is_archive(path: &str) -> bool {
+    archive_kind(path).is_some()
+}
+
+fn archive_kind(path: &str) -> Option<ArchiveKind> {
+    let lower = path.to_lowercase();
+
+    if lower.ends_with(".tar.gz") || lower.ends_with(".tgz") {
+        Some(ArchiveKind::TarGz)
+    } else if lower.ends_with(".tar") {
+        Some(ArchiveKind::Tar)
+    } else if lower.ends_with(".gz") {
+        Some(ArchiveKind::Gzip)
+    } else if lower.ends_with(".zip") || lower.ends_with(".jar") || lower.ends_with(".war") {
+        Some(ArchiveKind::Zip)
+    } else {
+        None
+    }
+}
+
+// Returns every regular file in the archive, expanding nested archives, with paths like `backup.zip!/config/.env`.
+pub fn extract_archive(path: &str, bytes: &[u8]) -> Result<Vec<ArchiveEntry>> {
+    let mut budget = ExtractBudget {
+        remaining_bytes: MAX_TOTAL_BYTES,
+        remaining_entries: MAX_ENTRIES,
+    };
+    let mut entries = Vec::new();
+
+    extract_into(path, bytes, 1, &mut budget, &mut entries)?;
+
+    if budget.exhausted() {
+        warn!("Stopped extracting {} after reaching the archive size limits", path);
+    }
+
+    Ok(entries)
+}
+
+fn extract_into(
+    path: &str,
+    bytes: &[u8],
+    depth: usize,
+    budget: &mut ExtractBudget,
+    entries: &mut Vec<ArchiveEntry>,
+) -> Result<()> {
+    let Some(kind) = archive_kind(path) else {
+        return Ok(());
+    };
+
+    let inner = match kind {
+        ArchiveKind::Zip => read_zip(bytes, budget)?,
+        ArchiveKind::Tar => read_tar(Cursor::new(bytes), budget)?,
+        ArchiveKind::TarGz => read_tar(GzDecoder::new(bytes), budget)?,
+        ArchiveKind::Gzip => {
+            let file_name = path.rsplit(['/', '!']).next().unwrap_or(path);
+            let name = file_name[..file_name.len() - 3].to_string();
+            read_limited(GzDecoder::new(bytes), budget)?
+                .map(|content| vec![(name, content)])
+                .unwrap_or_default()
+        }
+    };
+
+    for (name, content) in inner {
+        let nested_path = format!("{}{}{}", path, NESTED_PATH_SEPARATOR, name);
+
+        if is_archive(&nested_path) && depth < MAX_ARCHIVE_DEPTH {
+            if let Err(e) = extract_into(&nested_path, &content, depth + 1, budget, entries) {
+                debug!("Could not read nested archive {}: {}", nested_path, e);
+            }
+        } else {
+            entries.push(ArchiveEntry {
+                path: nested_path,
+                content,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+fn read_zip(bytes: &[u8], budget: &mut ExtractBudget) -> Result<Vec<(String, Vec<u8>)>> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(bytes))?;
+    let mut files = Vec::new();
+
+    for idx in 0..archive.len() {
+        if budget.exhausted() {
+            break;
+        }
+
+        // Encrypted or unsupported entries are skipped rather than failing the whole archive.
+        let file = match archive.by_index(idx) {
+            Ok(file) => file,
+            Err(e) => {
+                debug!("Skipping zip entry {}: {}", idx, e);
+                continue;
+            }
+        };
+        if file.is_dir() {
+            continue;
+        }
+
+        let name = file.name().to_string();
+        if let Some(content) = read_limited(file, budget)? {
+            files.push((name, content));
+        }
+    }
+
+    Ok(files)
+}
+
+fn read_tar<R: Read>(reader: R, budget: &mut ExtractBudget) -> Result<Vec<(String, Vec<u8>)>> {
+    let mut archive = tar::Archive::new(reader);
+    let mut files = Vec::new();
+
+    for entry in archive.entries()? {
+        if budget.exhausted() {
+            break;
+        }
+
+        let entry = entry?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+
+        let name = entry.path()?.to_string_lossy().into_owned();
+        if let Some(content) = read_limited(entry, budget)? {
+            files.push((name, content));
+        }
+    }
+
+    Ok(files)
+}
+
+fn read_limited<R: Read>(reader: R, budget: &mut ExtractBudget) -> Result<Option<Vec<u8>>> {
+    let limit = budget.remaining_bytes.min(MAX_ENTRY_BYTES);
+    let mut content = Vec::new();
+    reader.take(limit + 1).read_to_end(&mut content)?;
+
+    // Oversized entries still count against the budget since they were decompressed up to the limit.
+    budget.remaining_entries = budget.remaining_entries.saturating_sub(1);
+    budget.remaining_bytes = budget.remaining_bytes.saturating_sub(content.len() as u64);
+
+    if content.len() as u64 > limit {
+        debug!("Skipping archive entry larger than {} bytes", limit);
+        return Ok(None);
+    }
+
+    Ok(Some(content))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use zip::write::SimpleFileOptions;
+
+    fn zip(files: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, content) in files {
+            writer.start_file(*name, SimpleFileOptions::default()).unwrap();
+            writer.write_all(content).unwrap();
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    fn tar_gz(files: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default()));
+        for (name, content) in files {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            builder.append_data(&mut header, name, *content).unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap()
+    }
+
+    fn paths(entries: &[ArchiveEntry]) -> Vec<&str> {
+        entries.iter().map(|entry| entry.path.as_str()).collect()
+    }
+
+    #[test]
+    fn extracts_zip_and_tar_gz_entries() {
+        let entries = extract_archive("backup.zip", &zip(&[("config/.env", b"KEY=1"), ("README", b"hi")])).unwrap();
+        assert_eq!(paths(&entries), ["backup.zip!/config/.env", "backup.zip!/README"]);
+        assert_eq!(entries[0].content, b"KEY=1");
+
+        let entries = extract_archive("site.tgz", &tar_gz(&[("app/settings.py", b"DEBUG = False")])).unwrap();
+        assert_eq!(paths(&entries), ["site.tgz!/app/settings.py"]);
+        assert_eq!(entries[0].content, b"DEBUG = False");
+    }
+
+    #[test]
+    fn expands_nested_archives_up_to_the_depth_limit() {
+        let level4 = zip(&[("four.txt", b"4")]);
+        let level3 = zip(&[("three.txt", b"3"), ("level4.zip", &level4)]);
+        let level2 = tar_gz(&[("two.txt", b"2"), ("level3.zip", &level3)]);
+        let level1 = zip(&[("one.txt", b"1"), ("level2.tar.gz", &level2)]);
+
+        let entries = extract_archive("level1.zip", &level1).unwrap();
+
+        assert_eq!(paths(&entries), [
+            "level1.zip!/one.txt",
+            "level1.zip!/level2.tar.gz!/two.txt",
+            "level1.zip!/level2.tar.gz!/level3.zip!/three.txt",
+            // Past the depth limit the archive itself is returned unopened.
+            "level1.zip!/level2.tar.gz!/level3.zip!/level4.zip",
+        ]);
+        assert_eq!(entries[3].content, level4);
+    }
+
+    #[test]
+    fn stops_at_the_shared_entry_and_byte_budget() {
+        let inner = zip(&[("a.txt", b"aaaa"), ("b.txt", b"bbbb")]);
+        let outer = zip(&[("first.txt", b"1111"), ("inner.zip", &inner), ("last.txt", b"2222")]);
+
+        // The nested archive draws on the same entry budget as its parent.
+        let mut budget = ExtractBudget { remaining_bytes: MAX_TOTAL_BYTES, remaining_entries: 4 };
+        let mut entries = Vec::new();
+        extract_into("outer.zip", &outer, 1, &mut budget, &mut entries).unwrap();
+        assert_eq!(paths(&entries), ["outer.zip!/first.txt", "outer.zip!/inner.zip!/a.txt", "outer.zip!/last.txt"]);
+        assert!(budget.exhausted());
+
+        // An entry that would overrun the byte budget is dropped, and nothing is read after it.
+        let mut budget = ExtractBudget { remaining_bytes: 6, remaining_entries: MAX_ENTRIES };
+        let mut entries = Vec::new();
+        let files = zip(&[("small.txt", b"1234"), ("big.txt", b"12345678"), ("after.txt", b"1")]);
+        extract_into("files.zip", &files, 1, &mut budget, &mut entries).unwrap();
+        assert_eq!(paths(&entries), ["files.zip!/small.txt"]);
+        assert!(budget.exhausted());
+    }
+
+    #[test]
+    fn rejects_corrupt_archives() {
+        assert!(extract_archive("broken.zip", b"not a zip file").is_err());
+    }
+}
@@ -0,0 +1,526 @@
+use crate::utils::patterns::CONFIG_KEY_PATTERN;
+
+const MIN_SECRET_VALUE_LEN: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Env,
+    Yaml,
+    Json,
+    Toml,
+    Properties,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConfigEntry {
+    pub key_path: String,
+    pub value: String,
+    pub line_number: usize,
+}
+
+impl ConfigEntry {
+    // The last path segment carries the meaning: `services.db.environment.POSTGRES_PASSWORD` is a password.
+    pub fn key_name(&self) -> &str {
+        self.key_path.rsplit('.').next().unwrap_or(&self.key_path)
+    }
+
+    pub fn looks_secret(&self) -> bool {
+        !CONFIG_KEY_PATTERN.find_valid(self.key_name()).is_empty() && plausible_secret_value(&self.value)
+    }
+}
+
+pub fn config_format(path: &str) -> Option<ConfigFormat> {
+    let name = path.rsplit('/').next().unwrap_or(path).to_ascii_lowercase();
+
+    if name == ".env" || name.starts_with(".env.") || name.ends_with(".env") {
+        Some(ConfigFormat::Env)
+    } else if name.ends_with(".yml") || name.ends_with(".yaml") {
+        Some(ConfigFormat::Yaml)
+    } else if name.ends_with(".json") {
+        Some(ConfigFormat::Json)
+    } else if name.ends_with(".toml") {
+        Some(ConfigFormat::Toml)
+    } else if name.ends_with(".properties") {
+        Some(ConfigFormat::Properties)
+    } else {
+        None
+    }
+}
+
+pub fn parse_config(format: ConfigFormat, content: &str) -> Vec<ConfigEntry> {
+    match format {
+        ConfigFormat::Env => parse_key_values(content, &['='], &["#"]),
+        ConfigFormat::Properties => parse_key_values(content, &['=', ':'], &["#", "!"]),
+        ConfigFormat::Yaml => parse_yaml(content),
+        ConfigFormat::Json => parse_json(content),
+        ConfigFormat::Toml => parse_toml(content),
+    }
+}
+
+// Values that point somewhere else (`${DB_PASSWORD}`, `{{ .Values.token }}`) or can't be credentials are skipped.
+fn plausible_secret_value(value: &str) -> bool {
+    let is_reference = value.starts_with('$')
+        || value.contains("${")
+        || value.contains("{{")
+        || value.contains("%(")
+        || (value.starts_with('<') && value.ends_with('>'));
+    let is_path = value.starts_with('/') || value.starts_with("./") || value.starts_with("~/");
+    let is_number = value.chars().all(|c| c.is_ascii_digit() || matches!(c, '.' | '-' | ':'));
+
+    value.len() >= MIN_SECRET_VALUE_LEN
+        && !value.chars().any(char::is_whitespace)
+        && !is_reference
+        && !is_path
+        && !is_number
+}
+
+// `.env` and `.properties` files: one `KEY=value` per line.
+fn parse_key_values(content: &str, separators: &[char], comments: &[&str]) -> Vec<ConfigEntry> {
+    content
+        .lines()
+        .enumerate()
+        .filter_map(|(idx, line)| {
+            let line = line.trim();
+            if line.is_empty() || comments.iter().any(|c| line.starts_with(c)) {
+                return None;
+            }
+
+            let line = line.strip_prefix("export ").unwrap_or(line);
+            let (key, value) = line.split_once(separators)?;
+            let key = key.trim();
+            if key.is_empty() || key.contains(char::is_whitespace) {
+                return None;
+            }
+
+            Some(ConfigEntry {
+                key_path: key.to_string(),
+                value: scalar_value(value),
+                line_number: idx + 1,
+            })
+        })
+        .collect()
+}
+
+fn parse_toml(content: &str) -> Vec<ConfigEntry> {
+    let mut entries = Vec::new();
+    let mut table = String::new();
+
+    for (idx, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if line.starts_with('[') {
+            table = line
+                .trim_matches(|c| c == '[' || c == ']')
+                .split('.')
+                .map(|segment| unquote(segment.trim()))
+                .collect::<Vec<_>>()
+                .join(".");
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let value = value.trim();
+        if !value.starts_with(['"', '\'']) {
+            continue;
+        }
+
+        let key = key.split('.').map(|segment| unquote(segment.trim())).collect::<Vec<_>>().join(".");
+        entries.push(ConfigEntry {
+            key_path: join_path(&table, &key),
+            value: scalar_value(value),
+            line_number: idx + 1,
+        });
+    }
+
+    entries
+}
+
+// A line-oriented reader for the YAML found in config and compose files: block mappings, sequences of
+// `KEY=value` strings, and Kubernetes-style `- name: X` / `value: Y` pairs. Flow collections and
+// multi-line scalars are not expanded.
+fn parse_yaml(content: &str) -> Vec<ConfigEntry> {
+    let mut entries = Vec::new();
+    let mut parents: Vec<(usize, String)> = Vec::new();
+    let mut block_scalar_indent: Option<usize> = None;
+    let mut item_name: Option<(usize, String)> = None;
+
+    for (idx, raw_line) in content.lines().enumerate() {
+        let trimmed = raw_line.trim_start();
+        if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with("---") || trimmed.starts_with("...") {
+            continue;
+        }
+
+        let mut indent = raw_line.len() - trimmed.len();
+        if let Some(block_indent) = block_scalar_indent {
+            if indent > block_indent {
+                continue;
+            }
+            block_scalar_indent = None;
+        }
+
+        let mut line = trimmed;
+        let mut sequence_item = false;
+        if let Some(item) = line.strip_prefix("- ").or_else(|| (line == "-").then_some("")) {
+            sequence_item = true;
+            indent += line.len() - item.len();
+            line = item.trim_start();
+        }
+
+        parents.retain(|(parent_indent, _)| *parent_indent < indent);
+        if item_name.as_ref().is_some_and(|(name_indent, _)| *name_indent > indent || sequence_item) {
+            item_name = None;
+        }
+        let parent_path = parents.iter().map(|(_, key)| key.as_str()).collect::<Vec<_>>().join(".");
+
+        let Some((key, value)) = split_yaml_key(line) else {
+            if sequence_item && let Some((key, value)) = line.split_once('=') {
+                entries.push(ConfigEntry {
+                    key_path: join_path(&parent_path, unquote(key.trim())),
+                    value: scalar_value(value),
+                    line_number: idx + 1,
+                });
+            }
+            continue;
+        };
+
+        let value = value.trim();
+        if value.is_empty() || (value.starts_with('&') && !value.contains(' ')) {
+            parents.push((indent, key.to_string()));
+            continue;
+        }
+        if value.starts_with('|') || value.starts_with('>') {
+            block_scalar_indent = Some(indent);
+            continue;
+        }
+        if value.starts_with('[') || value.starts_with('{') {
+            continue;
+        }
+
+        let value = scalar_value(value);
+        if key == "name" {
+            item_name = Some((indent, value.clone()));
+        }
+        let key = match &item_name {
+            Some((name_indent, name)) if key == "value" && *name_indent == indent => name.clone(),
+            _ => key.to_string(),
+        };
+
+        entries.push(ConfigEntry {
+            key_path: join_path(&parent_path, &key),
+            value,
+            line_number: idx + 1,
+        });
+    }
+
+    entries
+}
+
+fn split_yaml_key(line: &str) -> Option<(&str, &str)> {
+    if let Some(quote) = line.chars().next().filter(|c| matches!(c, '"' | '\'')) {
+        let end = line[1..].find(quote)? + 1;
+        let rest = line[end + 1..].trim_start().strip_prefix(':')?;
+        return Some((&line[1..end], rest));
+    }
+
+    let (colon, _) = line
+        .match_indices(':')
+        .find(|(pos, _)| line[pos + 1..].is_empty() || line[pos + 1..].starts_with(' '))?;
+    let key = line[..colon].trim_end();
+    (!key.is_empty() && !key.contains(' ')).then_some((key, &line[colon + 1..]))
+}
+
+// serde_json keeps no positions, so once the file is known to parse, string values are read back with a small
+// tokenizer that tracks the line and key path in one pass.
+fn parse_json(content: &str) -> Vec<ConfigEntry> {
+    if serde_json::from_str::<serde::de::IgnoredAny>(content).is_err() {
+        return Vec::new();
+    }
+
+    let bytes = content.as_bytes();
+    let mut entries = Vec::new();
+    let mut frames: Vec<JsonFrame> = Vec::new();
+    let mut line_number = 1;
+    let mut pos = 0;
+
+    while pos < bytes.len() {
+        match bytes[pos] {
+            b'\n' => line_number += 1,
+            b'{' => frames.push(JsonFrame::Object(None)),
+            b'[' => frames.push(JsonFrame::Array(0)),
+            b'}' | b']' => {
+                frames.pop();
+            }
+            b',' => match frames.last_mut() {
+                Some(JsonFrame::Object(key)) => *key = None,
+                Some(JsonFrame::Array(idx)) => *idx += 1,
+                None => {}
+            },
+            b'"' => {
+                let start = pos;
+                pos += 1;
+                while pos < bytes.len() && bytes[pos] != b'"' {
+                    pos += if bytes[pos] == b'\\' { 2 } else { 1 };
+                }
+                let text: String = serde_json::from_str(&content[start..=pos]).unwrap_or_default();
+
+                match frames.last_mut() {
+                    Some(JsonFrame::Object(key @ None)) => *key = Some(text),
+                    Some(JsonFrame::Object(Some(_))) => entries.push(ConfigEntry {
+                        key_path: json_path(&frames),
+                        value: text,
+                        line_number,
+                    }),
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+        pos += 1;
+    }
+
+    entries
+}
+
+enum JsonFrame {
+    // The key whose value is being read, once it has been seen.
+    Object(Option<String>),
+    Array(usize),
+}
+
+fn json_path(frames: &[JsonFrame]) -> String {
+    let mut path = String::new();
+    for frame in frames {
+        match frame {
+            JsonFrame::Object(Some(key)) => path = join_path(&path, key),
+            JsonFrame::Object(None) => {}
+            JsonFrame::Array(idx) => path.push_str(&format!("[{}]", idx)),
+        }
+    }
+    path
+}
+
+fn scalar_value(value: &str) -> String {
+    let value = value.trim();
+    if value.starts_with(['"', '\'']) {
+        let quote = value.chars().next().unwrap_or('"');
+        return match value[1..].find(quote) {
+            Some(end) => value[1..end + 1].to_string(),
+            None => value[1..].to_string(),
+        };
+    }
+
+    match value.find(" #") {
+        Some(comment) => value[..comment].trim_end().to_string(),
+        None => value.to_string(),
+    }
+}
+
+fn unquote(value: &str) -> &str {
+    value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .or_else(|| value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+        .unwrap_or(value)
+}
+
+fn join_path(parent: &str, key: &str) -> String {
+    if parent.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", parent, key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries(format: ConfigFormat, content: &str) -> Vec<(String, String, usize)> {
+        parse_config(format, content)
+            .into_iter()
+            .map(|e| (e.key_path, e.value, e.line_number))
+            .collect()
+    }
+
+    fn entry(key_path: &str, value: &str, line_number: usize) -> (String, String, usize) {
+        (key_path.to_string(), value.to_string(), line_number)
+    }
+
+    #[test]
+    fn detects_formats_from_file_names() {
+        let cases = [
+            (".env", Some(ConfigFormat::Env)),
+            ("app/.env.production", Some(ConfigFormat::Env)),
+            ("deploy/prod.env", Some(ConfigFormat::Env)),
+            ("docker-compose.YML", Some(ConfigFormat::Yaml)),
+            ("k8s/secret.yaml", Some(ConfigFormat::Yaml)),
+            ("config/appsettings.json", Some(ConfigFormat::Json)),
+            ("Cargo.toml", Some(ConfigFormat::Toml)),
+            ("src/main/resources/application.properties", Some(ConfigFormat::Properties)),
+            ("environment.rs", None),
+            ("README.md", None),
+        ];
+        for (path, expected) in cases {
+            assert_eq!(config_format(path), expected, "{}", path);
+        }
+    }
+
+    #[test]
+    fn parses_env_files() {
+        let content = "# comment\n\nexport DB_PASSWORD=\"hunter2hunter2\"\nAPI_KEY=abc123 # trailing\nbad key=x\nNOVALUE\n";
+        assert_eq!(
+            entries(ConfigFormat::Env, content),
+            vec![entry("DB_PASSWORD", "hunter2hunter2", 3), entry("API_KEY", "abc123", 4)]
+        );
+    }
+
+    #[test]
+    fn parses_properties_files() {
+        let content = "! comment\n# comment\ndb.password = s3cr3tpass\nmail.token: 'tok-123'\n";
+        assert_eq!(
+            entries(ConfigFormat::Properties, content),
+            vec![entry("db.password", "s3cr3tpass", 3), entry("mail.token", "tok-123", 4)]
+        );
+    }
+
+    #[test]
+    fn parses_toml_tables_and_dotted_keys() {
+        let content = "title = \"app\"\nport = 8080\n\n[database]\npassword = \"s3cr3tpass\"\n\n[\"api\".\"keys\"]\nstripe.secret = 'sk_x'\n";
+        assert_eq!(
+            entries(ConfigFormat::Toml, content),
+            vec![
+                entry("title", "app", 1),
+                entry("database.password", "s3cr3tpass", 5),
+                entry("api.keys.stripe.secret", "sk_x", 8),
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_nested_yaml_mappings() {
+        let content = "\
+database:
+  primary:
+    password: \"s3cr3tpass\"  # quoted
+    host: db.internal
+  script: |
+    password: not-a-key
+  replica:
+    password: r3plicapass
+";
+        assert_eq!(
+            entries(ConfigFormat::Yaml, content),
+            vec![
+                entry("database.primary.password", "s3cr3tpass", 3),
+                entry("database.primary.host", "db.internal", 4),
+                entry("database.replica.password", "r3plicapass", 8),
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_compose_environment_sequences() {
+        let content = "\
+services:
+  db:
+    environment:
+      - POSTGRES_USER=app
+      - POSTGRES_PASSWORD=s3cr3tpass
+";
+        assert_eq!(
+            entries(ConfigFormat::Yaml, content),
+            vec![
+                entry("services.db.environment.POSTGRES_USER", "app", 4),
+                entry("services.db.environment.POSTGRES_PASSWORD", "s3cr3tpass", 5),
+            ]
+        );
+    }
+
+    #[test]
+    fn pairs_kubernetes_env_names_with_values() {
+        let content = "\
+env:
+  - name: API_TOKEN
+    value: tok-abcdef123
+  - name: LOG_LEVEL
+    value: debug
+";
+        let parsed = entries(ConfigFormat::Yaml, content);
+        assert!(parsed.contains(&entry("env.API_TOKEN", "tok-abcdef123", 3)), "{:?}", parsed);
+        assert!(parsed.contains(&entry("env.LOG_LEVEL", "debug", 5)), "{:?}", parsed);
+    }
+
+    #[test]
+    fn parses_json_with_paths_and_line_numbers() {
+        let content = r#"{
+  "name": "app",
+  "db": { "password": "s3cr3tpass", "port": 5432 },
+  "clients": [
+    { "secret": "first-secret" },
+    { "note": "say \"hi\"",
+      "secret": "second-secret" }
+  ],
+  "tags": ["password"]
+}"#;
+        assert_eq!(
+            entries(ConfigFormat::Json, content),
+            vec![
+                entry("name", "app", 2),
+                entry("db.password", "s3cr3tpass", 3),
+                entry("clients[0].secret", "first-secret", 5),
+                entry("clients[1].note", "say \"hi\"", 6),
+                entry("clients[1].secret", "second-secret", 7),
+            ]
+        );
+        assert!(entries(ConfigFormat::Json, "{ not json").is_empty());
+    }
+
+    #[test]
+    fn parses_large_json_in_one_pass() {
+        let body: Vec<String> = (0..20_000).map(|i| format!("  \"pkg{}\": {{ \"version\": \"1.0.{}\" }}", i, i)).collect();
+        let content = format!("{{\n{}\n}}", body.join(",\n"));
+        let parsed = parse_config(ConfigFormat::Json, &content);
+        assert_eq!(parsed.len(), 20_000);
+        assert_eq!(parsed[19_999].line_number, 20_001);
+        assert_eq!(parsed[19_999].key_path, "pkg19999.version");
+    }
+
+    #[test]
+    fn key_name_is_the_last_path_segment() {
+        let entry = ConfigEntry {
+            key_path: "services.db.environment.POSTGRES_PASSWORD".to_string(),
+            value: "s3cr3tpass".to_string(),
+            line_number: 1,
+        };
+        assert_eq!(entry.key_name(), "POSTGRES_PASSWORD");
+        assert_eq!(join_path("", "a"), "a");
+        assert_eq!(join_path("a.b", "c"), "a.b.c");
+    }
+
+    #[test]
+    fn rejects_implausible_secret_values() {
+        let cases = [
+            ("s3cr3tpass", true),
+            ("short", false),
+            ("has a space", false),
+            ("${DB_PASSWORD}", false),
+            ("$DB_PASSWORD", false),
+            ("{{ .Values.token }}", false),
+            ("%(password)s", false),
+            ("<your-password>", false),
+            ("/run/secrets/db", false),
+            ("./secrets/db.txt", false),
+            ("~/.ssh/id_rsa", false),
+            ("2024-01-01", false),
+            ("12345678", false),
+        ];
+        for (value, expected) in cases {
+            assert_eq!(plausible_secret_value(value), expected, "{}", value);
+        }
+    }
+}
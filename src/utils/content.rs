@@ -0,0 +1,135 @@
+const SNIFF_BYTES: usize = 8192;
+const MAX_CONTROL_RATIO: f64 = 0.1;
+
+const MAGIC_NUMBERS: &[(&[u8], &str)] = &[
+    (b"\x89PNG\r\n\x1a\n", "PNG image"),
+    (b"\xff\xd8\xff", "JPEG image"),
+    (b"GIF87a", "GIF image"),
+    (b"GIF89a", "GIF image"),
+    (b"%PDF-", "PDF document"),
+    (b"\x7fELF", "ELF executable"),
+    (b"\xcf\xfa\xed\xfe", "Mach-O executable"),
+    (b"\xca\xfe\xba\xbe", "Java class or Mach-O universal binary"),
+    (b"\x00asm", "WebAssembly module"),
+    (b"PK\x03\x04", "zip archive"),
+    (b"\x1f\x8b", "gzip data"),
+    (b"SQLite format 3\x00", "SQLite database"),
+    (b"\x28\xb5\x2f\xfd", "zstd data"),
+    (b"\xfd7zXZ\x00", "xz data"),
+    (b"RIFF", "RIFF media"),
+    (b"OggS", "Ogg media"),
+    (b"wOFF", "WOFF font"),
+    (b"wOF2", "WOFF2 font"),
+];
+
+pub enum Sniffed {
+    Text(String),
+    Binary(&'static str),
+}
+
+// Decides text vs binary from the bytes themselves rather than the file extension.
+pub fn sniff(bytes: &[u8]) -> Sniffed {
+    if let Some(text) = decode_utf16(bytes) {
+        return Sniffed::Text(text);
+    }
+
+    let bytes = bytes.strip_prefix(b"\xef\xbb\xbf").unwrap_or(bytes);
+
+    if let Some((_, kind)) = MAGIC_NUMBERS.iter().find(|(magic, _)| bytes.starts_with(magic)) {
+        return Sniffed::Binary(kind);
+    }
+
+    let head = &bytes[..bytes.len().min(SNIFF_BYTES)];
+    if head.contains(&0) {
+        return Sniffed::Binary("NUL bytes");
+    }
+
+    let control = head
+        .iter()
+        .filter(|&&b| b < 0x20 && !matches!(b, b'\n' | b'\r' | b'\t' | 0x0c | 0x1b))
+        .count();
+    if !head.is_empty() && control as f64 / head.len() as f64 > MAX_CONTROL_RATIO {
+        return Sniffed::Binary("control characters");
+    }
+
+    Sniffed::Text(String::from_utf8_lossy(bytes).into_owned())
+}
+
+// UTF-16 is recognised by its BOM, or by NUL high bytes on most ASCII characters.
+fn decode_utf16(bytes: &[u8]) -> Option<String> {
+    let (little_endian, body) = if let Some(body) = bytes.strip_prefix(b"\xff\xfe") {
+        (true, body)
+    } else if let Some(body) = bytes.strip_prefix(b"\xfe\xff") {
+        (false, body)
+    } else {
+        let head = &bytes[..bytes.len().min(SNIFF_BYTES) & !1];
+        if head.len() < 4 {
+            return None;
+        }
+
+        let pairs = head.len() / 2;
+        let even_nuls = head.iter().step_by(2).filter(|&&b| b == 0).count();
+        let odd_nuls = head.iter().skip(1).step_by(2).filter(|&&b| b == 0).count();
+
+        if odd_nuls * 10 >= pairs * 9 && even_nuls == 0 {
+            (true, bytes)
+        } else if even_nuls * 10 >= pairs * 9 && odd_nuls == 0 {
+            (false, bytes)
+        } else {
+            return None;
+        }
+    };
+
+    let units: Vec<u16> = body
+        .chunks_exact(2)
+        .map(|pair| {
+            if little_endian {
+                u16::from_le_bytes([pair[0], pair[1]])
+            } else {
+                u16::from_be_bytes([pair[0], pair[1]])
+            }
+        })
+        .collect();
+
+    Some(String::from_utf16_lossy(&units))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(bytes: &[u8]) -> Option<String> {
+        match sniff(bytes) {
+            Sniffed::Text(text) => Some(text),
+            Sniffed::Binary(_) => None,
+        }
+    }
+
+    fn utf16(text: &str, little_endian: bool) -> Vec<u8> {
+        text.encode_utf16()
+            .flat_map(|unit| if little_endian { unit.to_le_bytes() } else { unit.to_be_bytes() })
+            .collect()
+    }
+
+    #[test]
+    fn decodes_utf16_with_or_without_a_bom() {
+        let big_endian = [b"\xfe\xff".as_slice(), &utf16("key = value", false)].concat();
+        assert_eq!(text(&big_endian).as_deref(), Some("key = value"));
+        assert_eq!(text(&utf16("key = value", true)).as_deref(), Some("key = value"));
+        assert_eq!(text(&utf16("key = value", false)).as_deref(), Some("key = value"));
+    }
+
+    #[test]
+    fn strips_the_utf8_bom() {
+        assert_eq!(text(b"\xef\xbb\xbfkey = value").as_deref(), Some("key = value"));
+    }
+
+    #[test]
+    fn detects_binary_from_magic_numbers_nul_bytes_and_control_characters() {
+        assert!(matches!(sniff(b"\x7fELF\x02\x01\x01"), Sniffed::Binary("ELF executable")));
+        assert!(matches!(sniff(b"\xef\xbb\xbf%PDF-1.7"), Sniffed::Binary("PDF document")));
+        assert!(matches!(sniff(b"header\0\0body"), Sniffed::Binary("NUL bytes")));
+        assert!(matches!(sniff(b"\x01\x02\x03\x04abc"), Sniffed::Binary("control characters")));
+        assert!(text(b"\x1b[31mred\x1b[0m\tok\r\n").is_some());
+    }
+}
@@ -0,0 +1,164 @@
+use crate::models::scan::Encoding;
+use base64::Engine as _;
+use base64::engine::general_purpose::{STANDARD_NO_PAD, URL_SAFE_NO_PAD};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::ops::Range;
+
+pub const MAX_DECODE_DEPTH: usize = 3;
+const MIN_DECODED_LEN: usize = 8;
+const CONTEXT_BYTES: usize = 128;
+const MAX_ENCODED_BYTES: usize = 16 * 1024;
+const MAX_CANDIDATES_PER_LINE: usize = 256;
+const MAX_RESULTS_PER_LINE: usize = 32;
+
+lazy_static! {
+    static ref BASE64_CANDIDATE: Regex = Regex::new(r"[A-Za-z0-9+/_-]{16,}={0,2}").unwrap();
+    static ref HEX_CANDIDATE: Regex = Regex::new(r"\b(?:[0-9a-fA-F]{2}){8,}\b").unwrap();
+    static ref PERCENT_CANDIDATE: Regex = Regex::new(r"[^\s%]*(?:%[0-9A-Fa-f]{2}[^\s%]*)+").unwrap();
+}
+
+#[derive(Debug, Clone)]
+pub struct DecodedText {
+    pub text: String,
+    pub chain: Vec<Encoding>,
+}
+
+// Each result is the encoded substring replaced by its decoded form, with up to CONTEXT_BYTES of the line on each
+// side so rules still see the surrounding context (`aws_secret_access_key: <decoded>`). Decoded text is searched
+// again, up to MAX_DECODE_DEPTH. Minified one-line bundles are full of base64-like identifiers, so the number of
+// candidates tried and results returned per line is capped.
+pub fn decode_line(line: &str) -> Vec<DecodedText> {
+    let mut results = Vec::new();
+    let mut attempts = MAX_CANDIDATES_PER_LINE;
+    decode_region(line, 0..line.len(), &[], &mut results, &mut attempts);
+    results
+}
+
+fn decode_region(text: &str, region: Range<usize>, chain: &[Encoding], results: &mut Vec<DecodedText>, attempts: &mut usize) {
+    if chain.len() >= MAX_DECODE_DEPTH {
+        return;
+    }
+
+    let slice = &text[region.clone()];
+    let candidates = [
+        (Encoding::Base64, &*BASE64_CANDIDATE),
+        (Encoding::Hex, &*HEX_CANDIDATE),
+        (Encoding::Percent, &*PERCENT_CANDIDATE),
+    ]
+    .into_iter()
+    .flat_map(|(encoding, regex)| regex.find_iter(slice).map(move |mat| (encoding, mat)));
+
+    for (encoding, mat) in candidates {
+        if *attempts == 0 || results.len() >= MAX_RESULTS_PER_LINE {
+            return;
+        }
+        if mat.len() > MAX_ENCODED_BYTES {
+            continue;
+        }
+        *attempts -= 1;
+
+        let Some(decoded) = decode(encoding, mat.as_str()) else {
+            continue;
+        };
+
+        let start = region.start + mat.start();
+        let end = region.start + mat.end();
+        let before = text.floor_char_boundary(start.saturating_sub(CONTEXT_BYTES));
+        let after = text.ceil_char_boundary((end + CONTEXT_BYTES).min(text.len()));
+        let substituted = format!("{}{}{}", &text[before..start], decoded, &text[end..after]);
+        let decoded_start = start - before;
+        let mut decoded_chain = chain.to_vec();
+        decoded_chain.push(encoding);
+
+        decode_region(&substituted, decoded_start..decoded_start + decoded.len(), &decoded_chain, results, attempts);
+        results.push(DecodedText {
+            text: substituted,
+            chain: decoded_chain,
+        });
+    }
+}
+
+fn decode(encoding: Encoding, encoded: &str) -> Option<String> {
+    let bytes = match encoding {
+        Encoding::Base64 => {
+            let trimmed = encoded.trim_end_matches('=');
+            if trimmed.contains(['-', '_']) {
+                URL_SAFE_NO_PAD.decode(trimmed).ok()?
+            } else {
+                STANDARD_NO_PAD.decode(trimmed).ok()?
+            }
+        }
+        Encoding::Hex => hex::decode(encoded).ok()?,
+        Encoding::Percent => percent_decode(encoded)?,
+    };
+
+    let decoded = String::from_utf8(bytes).ok()?;
+    let printable = decoded
+        .chars()
+        .all(|c| !c.is_control() || matches!(c, '\n' | '\r' | '\t'));
+
+    (printable && decoded.len() >= MIN_DECODED_LEN && decoded != encoded).then_some(decoded)
+}
+
+fn percent_decode(encoded: &str) -> Option<Vec<u8>> {
+    let bytes = encoded.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut idx = 0;
+
+    while idx < bytes.len() {
+        if bytes[idx] == b'%' {
+            let hex = encoded.get(idx + 1..idx + 3)?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            idx += 3;
+        } else {
+            decoded.push(bytes[idx]);
+            idx += 1;
+        }
+    }
+
+    Some(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::engine::general_purpose::STANDARD;
+
+    #[test]
+    fn decodes_nested_layers_with_context() {
+        let inner = STANDARD.encode("aws_secret_access_key=h8Jd2kQ9vLm4Xc7Rt1Wp6Zs3Nf0Bg5Yu2Ae9Ko4T");
+        let line = format!("data: {}", STANDARD.encode(&inner));
+
+        let results = decode_line(&line);
+        let nested = results.iter().find(|r| r.chain == [Encoding::Base64, Encoding::Base64]).unwrap();
+        assert_eq!(nested.text, "data: aws_secret_access_key=h8Jd2kQ9vLm4Xc7Rt1Wp6Zs3Nf0Bg5Yu2Ae9Ko4T");
+    }
+
+    #[test]
+    fn keeps_a_bounded_window_around_the_decoded_span() {
+        let encoded = STANDARD.encode("password=Tr0ub4dor&3-horse");
+        let line = format!("{}{} {}", "x ".repeat(5_000), encoded, "y ".repeat(5_000));
+
+        let results = decode_line(&line);
+        let result = results.iter().find(|r| r.text.contains("password=Tr0ub4dor&3-horse")).unwrap();
+        assert!(result.text.len() <= 2 * CONTEXT_BYTES + 32, "{} bytes", result.text.len());
+    }
+
+    #[test]
+    fn window_respects_char_boundaries() {
+        let encoded = STANDARD.encode("api_key=Tr0ub4dor&3-horse");
+        let line = format!("{} {} {}", "é".repeat(200), encoded, "ü".repeat(200));
+        assert!(decode_line(&line).iter().any(|r| r.text.contains("api_key=Tr0ub4dor&3-horse")));
+    }
+
+    #[test]
+    fn caps_results_on_long_lines() {
+        let chunk = STANDARD.encode("some decodable text here");
+        let line = vec![chunk; 10_000].join(" ");
+
+        let results = decode_line(&line);
+        assert!(results.len() <= MAX_RESULTS_PER_LINE);
+        assert!(results.iter().all(|r| r.text.len() <= 2 * CONTEXT_BYTES + 64));
+    }
+}
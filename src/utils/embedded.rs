@@ -0,0 +1,158 @@
+use crate::utils::archive::NESTED_PATH_SEPARATOR;
+use serde_json::Value;
+
+// A text that a JSON container file carries inside string values, scanned on its own so line numbers mean something.
+pub struct EmbeddedDocument {
+    pub path: String,
+    pub cell_index: Option<usize>,
+    pub content: String,
+}
+
+pub fn has_embedded_documents(path: &str) -> bool {
+    let path = path.to_ascii_lowercase();
+    path.ends_with(".ipynb") || path.ends_with(".map")
+}
+
+// Returns None when the file is not a notebook or source map, or doesn't parse as one.
+pub fn embedded_documents(path: &str, content: &str) -> Option<Vec<EmbeddedDocument>> {
+    if !has_embedded_documents(path) {
+        return None;
+    }
+
+    let root: Value = serde_json::from_str(content).ok()?;
+    if path.to_ascii_lowercase().ends_with(".ipynb") {
+        notebook_cells(path, &root)
+    } else {
+        source_map_sources(path, &root)
+    }
+}
+
+// Each cell becomes one document: its source, then the text of its outputs, so line numbers are relative to the cell.
+fn notebook_cells(path: &str, root: &Value) -> Option<Vec<EmbeddedDocument>> {
+    let cells = root.get("cells")?.as_array()?;
+
+    Some(
+        cells
+            .iter()
+            .enumerate()
+            .map(|(idx, cell)| {
+                let mut parts = vec![joined_text(&cell["source"])];
+                for output in cell["outputs"].as_array().into_iter().flatten() {
+                    parts.push(joined_text(&output["text"]));
+                    parts.push(joined_text(&output["traceback"]));
+                    for (mime, data) in output["data"].as_object().into_iter().flatten() {
+                        if mime.starts_with("text/") || mime.ends_with("json") || mime.ends_with("javascript") {
+                            parts.push(joined_text(data));
+                        }
+                    }
+                }
+                parts.retain(|part| !part.is_empty());
+
+                EmbeddedDocument {
+                    path: path.to_string(),
+                    cell_index: Some(idx),
+                    content: parts.join("\n"),
+                }
+            })
+            .collect(),
+    )
+}
+
+fn source_map_sources(path: &str, root: &Value) -> Option<Vec<EmbeddedDocument>> {
+    let sources = root.get("sourcesContent")?.as_array()?;
+    let names = root["sources"].as_array();
+
+    Some(
+        sources
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, source)| {
+                let name = names
+                    .and_then(|names| names.get(idx))
+                    .and_then(Value::as_str)
+                    .map_or_else(|| format!("source-{}", idx), str::to_string);
+
+                Some(EmbeddedDocument {
+                    path: format!("{}{}{}", path, NESTED_PATH_SEPARATOR, name),
+                    cell_index: None,
+                    content: source.as_str()?.to_string(),
+                })
+            })
+            .collect(),
+    )
+}
+
+// Notebook text fields are either one string or a list of lines; source lines keep their newlines, traceback lines don't.
+fn joined_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.trim_end_matches('\n').to_string(),
+        Value::Array(lines) => {
+            let mut text = String::new();
+            for line in lines.iter().filter_map(Value::as_str) {
+                if !text.is_empty() && !text.ends_with('\n') {
+                    text.push('\n');
+                }
+                text.push_str(line);
+            }
+            text.trim_end_matches('\n').to_string()
+        }
+        Value::Object(_) => serde_json::to_string_pretty(value).unwrap_or_default(),
+        _ => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn splits_notebooks_into_cells_with_their_outputs() {
+        let notebook = json!({
+            "cells": [
+                { "cell_type": "markdown", "source": "# Setup\n" },
+                {
+                    "cell_type": "code",
+                    "source": ["import os\n", "client = connect()\n"],
+                    "outputs": [
+                        { "output_type": "stream", "text": ["connected\n"] },
+                        { "output_type": "error", "traceback": ["Traceback", "KeyError: 'token'"] },
+                        { "output_type": "display_data", "data": { "text/plain": "done", "image/png": "iVBORw0KGgo=" } }
+                    ]
+                }
+            ]
+        });
+
+        let cells = embedded_documents("analysis.ipynb", &notebook.to_string()).unwrap();
+
+        assert_eq!(cells.len(), 2);
+        assert_eq!(cells[0].cell_index, Some(0));
+        assert_eq!(cells[0].content, "# Setup");
+        assert_eq!(cells[1].cell_index, Some(1));
+        assert_eq!(cells[1].path, "analysis.ipynb");
+        assert_eq!(cells[1].content, "import os\nclient = connect()\nconnected\nTraceback\nKeyError: 'token'\ndone");
+    }
+
+    #[test]
+    fn names_source_map_entries_after_their_sources() {
+        let map = json!({
+            "version": 3,
+            "sources": ["webpack:///src/config.ts", "webpack:///src/empty.ts"],
+            "sourcesContent": ["export const a = 1;\n", null, "export const c = 3;\n"]
+        });
+
+        let sources = embedded_documents("dist/app.js.map", &map.to_string()).unwrap();
+
+        let paths: Vec<&str> = sources.iter().map(|source| source.path.as_str()).collect();
+        assert_eq!(paths, ["dist/app.js.map!/webpack:///src/config.ts", "dist/app.js.map!/source-2"]);
+        assert_eq!(sources[0].content, "export const a = 1;\n");
+        assert!(sources.iter().all(|source| source.cell_index.is_none()));
+    }
+
+    #[test]
+    fn leaves_other_files_and_unparseable_json_alone() {
+        assert!(embedded_documents("config.json", r#"{"cells": []}"#).is_none());
+        assert!(embedded_documents("broken.ipynb", "{ not json").is_none());
+        assert!(embedded_documents("bundle.js.map", r#"{"version": 3}"#).is_none());
+    }
+}
@@ -0,0 +1,47 @@
+use crate::models::scan::SkipReason;
+use anyhow::Result;
+use globset::{Glob, GlobSet, GlobSetBuilder};
+
+pub const DEFAULT_MAX_FILE_BYTES: usize = 1024 * 1024;
+
+pub struct FileFilter {
+    include: Option<GlobSet>,
+    exclude: GlobSet,
+    max_file_bytes: usize,
+}
+
+impl FileFilter {
+    pub fn new(include: &[String], exclude: &[String], max_file_bytes: usize) -> Result<Self> {
+        Ok(Self {
+            include: if include.is_empty() { None } else { Some(build_glob_set(include)?) },
+            exclude: build_glob_set(exclude)?,
+            max_file_bytes,
+        })
+    }
+
+    pub fn path_skip_reason(&self, path: &str) -> Option<SkipReason> {
+        if self.exclude.is_match(path) {
+            Some(SkipReason::Excluded)
+        } else if self.include.as_ref().is_some_and(|include| !include.is_match(path)) {
+            Some(SkipReason::NotIncluded)
+        } else {
+            None
+        }
+    }
+
+    pub fn exceeds_size_limit(&self, size: usize) -> bool {
+        size > self.max_file_bytes
+    }
+
+    pub fn max_file_bytes(&self) -> usize {
+        self.max_file_bytes
+    }
+}
+
+fn build_glob_set(patterns: &[String]) -> Result<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        builder.add(Glob::new(pattern)?);
+    }
+    Ok(builder.build()?)
+}
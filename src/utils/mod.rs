@@ -1 +1,11 @@
-pub mod patterns;
\ No newline at end of file
+pub mod allowlist;
+pub mod archive;
+pub mod config;
+pub mod content;
+pub mod decode;
+pub mod embedded;
+pub mod file_filter;
+pub mod logging;
+pub mod patterns;
+pub mod placeholder;
+pub mod redaction;
\ No newline at end of file